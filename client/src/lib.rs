@@ -0,0 +1,389 @@
+mod registry;
+mod verdict;
+
+pub use registry::{Attempt, DayRecord, PartRecord, Registry};
+pub use verdict::{parse_cooldown, Verdict};
+
+use common::Answer;
+use std::{
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+const SESSION_VARIABLE: &str = "AOC_SESSION";
+const USER_AGENT: &str = "github.com/Mike-Neto/advent-of-code-2022 client";
+
+#[derive(Debug)]
+pub enum Error {
+    IO(std::io::Error),
+    Json(serde_json::Error),
+    Http(u16, String),
+    Transport(String),
+    MissingSession,
+    UnknownResponse(String),
+    RateLimited(Duration),
+    Unsubmittable(Answer),
+}
+
+impl From<ureq::Error> for Error {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(code, response) => {
+                Self::Http(code, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(transport) => Self::Transport(transport.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    const fn level(self) -> &'static str {
+        match self {
+            Self::One => "1",
+            Self::Two => "2",
+        }
+    }
+}
+
+/// Talks to the puzzle server on behalf of a single session.
+///
+/// Every request is spaced at least `min_interval` apart, and after the
+/// server asks us to back off no answer is sent until the wait is over.
+#[derive(Debug)]
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+    last_request: Option<Instant>,
+    not_before: Option<Instant>,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: session.trim().to_string(),
+            cache_dir: PathBuf::from(".aoc-cache"),
+            min_interval: Duration::from_secs(5),
+            last_request: None,
+            not_before: None,
+        }
+    }
+
+    /// Builds a client from the session token in the `AOC_SESSION`
+    /// environment variable.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the variable is unset or empty.
+    pub fn from_env() -> Result<Self, Error> {
+        match std::env::var(SESSION_VARIABLE) {
+            Ok(session) if !session.trim().is_empty() => Ok(Self::new(&session)),
+            _ => Err(Error::MissingSession),
+        }
+    }
+
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Returns the puzzle input for `day`, downloading it only if it is not
+    /// cached on disk yet.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the request fails or the cache cannot be
+    /// read or written.
+    pub fn fetch_input(&mut self, day: u8) -> Result<String, Error> {
+        let cache_path = self
+            .cache_dir
+            .join(YEAR.to_string())
+            .join(format!("day_{day:02}.txt"));
+        if let Ok(input) = fs::read_to_string(&cache_path) {
+            return Ok(input);
+        }
+
+        self.throttle();
+        let input = self
+            .agent
+            .get(&format!("{}/{YEAR}/day/{day}/input", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?
+            .into_string()
+            .map_err(Error::IO)?;
+
+        if let Some(parent) = cache_path.parent() {
+            fs::create_dir_all(parent).map_err(Error::IO)?;
+        }
+        fs::write(&cache_path, &input).map_err(Error::IO)?;
+
+        Ok(input)
+    }
+
+    /// Submits `answer` for `day`/`part` and records the verdict in
+    /// `registry`. Answers the registry already knows about are not sent
+    /// again.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `answer` is not a single value, we are still
+    /// waiting out a cooldown, the request fails, the response cannot be
+    /// understood or the registry cannot be saved.
+    pub fn submit(
+        &mut self,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+        registry: &mut Registry,
+    ) -> Result<Verdict, Error> {
+        if matches!(answer, Answer::Image(_) | Answer::List(_)) {
+            return Err(Error::Unsubmittable(answer.clone()));
+        }
+        if let Some(accepted) = registry.accepted(day, part) {
+            if accepted == answer {
+                return Ok(Verdict::Correct);
+            }
+            return Ok(Verdict::AlreadySolved);
+        }
+        if let Some(verdict) = registry.previous_verdict(day, part, answer) {
+            return Ok(verdict.clone());
+        }
+        if let Some(not_before) = self.not_before {
+            let remaining = not_before.saturating_duration_since(Instant::now());
+            if !remaining.is_zero() {
+                return Err(Error::RateLimited(remaining));
+            }
+        }
+
+        self.throttle();
+        let body = self
+            .agent
+            .post(&format!("{}/{YEAR}/day/{day}/answer", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", part.level()), ("answer", &answer.to_string())])?
+            .into_string()
+            .map_err(Error::IO)?;
+
+        let verdict = Verdict::parse(&body).ok_or_else(|| Error::UnknownResponse(body.clone()))?;
+        let cooldown = match &verdict {
+            Verdict::Wait(wait) => Some(*wait),
+            _ => parse_cooldown(&body),
+        };
+        self.not_before = cooldown.map(|cooldown| Instant::now() + cooldown);
+
+        registry.record(day, part, answer, &verdict);
+        registry.save()?;
+
+        Ok(verdict)
+    }
+
+    fn throttle(&mut self) {
+        if let Some(remaining) = self
+            .last_request
+            .and_then(|last_request| self.min_interval.checked_sub(last_request.elapsed()))
+        {
+            thread::sleep(remaining);
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_cooldown, Client, Error, Part, Registry, Verdict};
+    use common::Answer;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        path::{Path, PathBuf},
+        sync::mpsc::{self, Receiver},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    /// Stand-in for the puzzle server: answers one connection per canned
+    /// body, in order, and hands back each raw request it received.
+    fn serve(bodies: Vec<&'static str>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for body in bodies {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                sender.send(request).unwrap();
+
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (base_url, receiver)
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-client-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn client(base_url: &str, dir: &Path) -> Client {
+        Client::new("secret")
+            .with_base_url(base_url)
+            .with_cache_dir(dir.join("cache"))
+            .with_min_interval(Duration::ZERO)
+    }
+
+    #[test]
+    fn fetch_input_is_cached() {
+        let dir = scratch_dir("fetch");
+        let (base_url, requests) = serve(vec!["1000\n2000\n"]);
+        let mut client = client(&base_url, &dir);
+
+        assert_eq!(client.fetch_input(1).unwrap(), "1000\n2000\n");
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input "));
+        assert!(request.contains("session=secret"));
+
+        // The stand-in server is gone, so this can only come from the cache.
+        assert_eq!(client.fetch_input(1).unwrap(), "1000\n2000\n");
+    }
+
+    #[test]
+    fn requests_are_spaced_out() {
+        let dir = scratch_dir("throttle");
+        let (base_url, _requests) = serve(vec!["a", "b"]);
+        let mut client = client(&base_url, &dir).with_min_interval(Duration::from_millis(200));
+
+        let start = Instant::now();
+        client.fetch_input(1).unwrap();
+        client.fetch_input(2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn submit_records_verdicts() {
+        let dir = scratch_dir("submit");
+        let (base_url, requests) = serve(vec![
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+            "<article><p>That's the right answer!</p></article>",
+        ]);
+        let mut client = client(&base_url, &dir);
+        let mut registry = Registry::open(dir.join("answers.json")).unwrap();
+
+        let verdict = client
+            .submit(3, Part::Two, &Answer::from(100), &mut registry)
+            .unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2022/day/3/answer "));
+        assert!(request.ends_with("level=2&answer=100"));
+
+        // Known answers are answered from the registry.
+        let verdict = client
+            .submit(3, Part::Two, &Answer::from(100), &mut registry)
+            .unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+
+        let verdict = client
+            .submit(3, Part::Two, &Answer::from(42), &mut registry)
+            .unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let registry = Registry::open(dir.join("answers.json")).unwrap();
+        assert_eq!(registry.accepted(3, Part::Two), Some(&Answer::from(42)));
+        assert_eq!(registry.get(3, Part::Two).unwrap().attempts.len(), 2);
+    }
+
+    #[test]
+    fn submit_waits_out_cooldown() {
+        let dir = scratch_dir("wait");
+        let (base_url, _requests) = serve(vec![
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. </p></article>",
+        ]);
+        let mut client = client(&base_url, &dir);
+        let mut registry = Registry::open(dir.join("answers.json")).unwrap();
+
+        let verdict = client
+            .submit(1, Part::One, &Answer::from(1), &mut registry)
+            .unwrap();
+        assert_eq!(verdict, Verdict::Wait(Duration::from_secs(65)));
+        assert!(registry.get(1, Part::One).is_none());
+
+        let result = client.submit(1, Part::One, &Answer::from(2), &mut registry);
+        assert!(matches!(result, Err(Error::RateLimited(_))));
+    }
+
+    #[test]
+    fn registry_loads_known_answers() {
+        let registry = Registry::open("../answers.json").unwrap();
+        let result = registry.accepted(5, Part::One);
+        assert_eq!(result, Some(&Answer::from("SHMSDGZVC")));
+        let result = registry.accepted(15, Part::Two);
+        assert_eq!(result, Some(&Answer::from(11_583_882_601_918_i64)));
+        let result = registry.accepted(10, Part::Two);
+        assert!(matches!(result, Some(Answer::Image(rows)) if rows.len() == 6));
+    }
+
+    #[test]
+    fn parse_cooldown_works() {
+        let result = parse_cooldown("please wait one minute before trying again.");
+        assert_eq!(result, Some(Duration::from_mins(1)));
+        let result = parse_cooldown("please wait 5 minutes before trying again.");
+        assert_eq!(result, Some(Duration::from_mins(5)));
+        let result = parse_cooldown("That's the right answer!");
+        assert_eq!(result, None);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{Error, Part, Verdict};
+use common::Answer;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub answer: Answer,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<Attempt>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayRecord {
+    #[serde(default)]
+    pub part_one: PartRecord,
+    #[serde(default)]
+    pub part_two: PartRecord,
+}
+
+impl DayRecord {
+    const fn part(&self, part: Part) -> &PartRecord {
+        match part {
+            Part::One => &self.part_one,
+            Part::Two => &self.part_two,
+        }
+    }
+
+    const fn part_mut(&mut self, part: Part) -> &mut PartRecord {
+        match part {
+            Part::One => &mut self.part_one,
+            Part::Two => &mut self.part_two,
+        }
+    }
+}
+
+/// Known answers and every submission made for them, persisted as JSON.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Registry {
+    #[serde(skip)]
+    path: PathBuf,
+    days: BTreeMap<u8, DayRecord>,
+}
+
+impl Registry {
+    /// Loads the registry stored at `path`, starting an empty one if the
+    /// file does not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `path` exists but cannot be read or does not
+    /// contain a valid registry.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let mut registry: Self = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(Error::Json)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(Error::IO(e)),
+        };
+        registry.path = path;
+
+        Ok(registry)
+    }
+
+    /// Writes the registry back to the file it was opened from.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the file cannot be written.
+    pub fn save(&self) -> Result<(), Error> {
+        let contents = serde_json::to_string_pretty(self).map_err(Error::Json)?;
+        fs::write(&self.path, contents + "\n").map_err(Error::IO)
+    }
+
+    #[must_use]
+    pub fn get(&self, day: u8, part: Part) -> Option<&PartRecord> {
+        self.days.get(&day).map(|record| record.part(part))
+    }
+
+    #[must_use]
+    pub fn accepted(&self, day: u8, part: Part) -> Option<&Answer> {
+        self.get(day, part)?.accepted.as_ref()
+    }
+
+    /// The verdict previously given for this exact `answer`, if it was
+    /// already submitted.
+    #[must_use]
+    pub fn previous_verdict(&self, day: u8, part: Part, answer: &Answer) -> Option<&Verdict> {
+        self.get(day, part)?
+            .attempts
+            .iter()
+            .find(|attempt| attempt.answer == *answer)
+            .map(|attempt| &attempt.verdict)
+    }
+
+    /// Records a judged submission. Verdicts that did not judge the answer
+    /// (rate limits, already solved) are not kept.
+    pub fn record(&mut self, day: u8, part: Part, answer: &Answer, verdict: &Verdict) {
+        if !verdict.is_judgement() {
+            return;
+        }
+
+        let record = self.days.entry(day).or_default().part_mut(part);
+        if *verdict == Verdict::Correct {
+            record.accepted = Some(answer.clone());
+        }
+        record.attempts.push(Attempt {
+            answer: answer.clone(),
+            verdict: verdict.clone(),
+        });
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Outcome of submitting an answer, as reported by the puzzle server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    AlreadySolved,
+    Wait(Duration),
+}
+
+impl Verdict {
+    /// Reads the verdict out of the HTML (or plain text) body returned by the
+    /// answer endpoint.
+    #[must_use]
+    pub fn parse(body: &str) -> Option<Self> {
+        if body.contains("That's the right answer") {
+            return Some(Self::Correct);
+        }
+        if body.contains("your answer is too high") {
+            return Some(Self::TooHigh);
+        }
+        if body.contains("your answer is too low") {
+            return Some(Self::TooLow);
+        }
+        if body.contains("That's not the right answer") {
+            return Some(Self::Incorrect);
+        }
+        if body.contains("You gave an answer too recently") {
+            return Some(Self::Wait(parse_time_left(body).unwrap_or_default()));
+        }
+        if body.contains("You don't seem to be solving the right level") {
+            return Some(Self::AlreadySolved);
+        }
+        None
+    }
+
+    /// Whether the server actually judged the answer, as opposed to
+    /// refusing to look at it.
+    #[must_use]
+    pub const fn is_judgement(&self) -> bool {
+        !matches!(self, Self::Wait(_) | Self::AlreadySolved)
+    }
+}
+
+/// Cooldown imposed after a wrong answer, e.g. "please wait one minute
+/// before trying again" or "please wait 5 minutes before trying again".
+#[must_use]
+pub fn parse_cooldown(body: &str) -> Option<Duration> {
+    let (_, rest) = body.split_once("please wait ")?;
+    let mut words = rest.split_whitespace();
+    let amount = match words.next()? {
+        "one" | "a" | "an" => 1,
+        value => value.parse().ok()?,
+    };
+    let seconds = match words.next()?.trim_end_matches('s') {
+        "second" => amount,
+        "minute" => amount * 60,
+        "hour" => amount * 60 * 60,
+        _ => return None,
+    };
+
+    Some(Duration::from_secs(seconds))
+}
+
+// You have 4m 12s left to wait.
+fn parse_time_left(body: &str) -> Option<Duration> {
+    let (_, rest) = body.split_once("You have ")?;
+    let (time, _) = rest.split_once(" left to wait")?;
+
+    time.split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "s" => Some(value),
+                "m" => Some(value * 60),
+                "h" => Some(value * 60 * 60),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
@@ -0,0 +1,183 @@
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt;
+
+/// The result of solving a puzzle part.
+///
+/// Integers share a single `i128` variant so that, for example, a `u64` and
+/// an `i64` holding the same value compare equal. A `u128` has to fit in it,
+/// so it only converts with [`TryFrom`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Rows of pixels, such as the CRT output of day ten.
+    Image(Vec<String>),
+    /// One answer per input line.
+    List(Vec<Self>),
+}
+
+impl Answer {
+    #[must_use]
+    pub fn image<I, S>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Image(rows.into_iter().map(Into::into).collect())
+    }
+
+    #[must_use]
+    pub const fn as_integer(&self) -> Option<i128> {
+        match self {
+            Self::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(value: $integer) -> Self {
+                    Self::Integer(i128::from(value))
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl TryFrom<u128> for Answer {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        i128::try_from(value).map(Self::Integer)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Integer(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Self::Integer(value as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl<T: Into<Self>> From<Vec<T>> for Answer {
+    fn from(values: Vec<T>) -> Self {
+        Self::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Text(value) => f.write_str(value),
+            Self::Image(rows) => f.write_str(&rows.join("\n")),
+            Self::List(values) => {
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// Integers are plain JSON numbers, text a string, lists an array and images
+// an `{"image": [rows]}` object so they can't be mistaken for a list.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Integer(value) => match i64::try_from(*value) {
+                Ok(value) => serializer.serialize_i64(value),
+                Err(_) => serializer.serialize_i128(*value),
+            },
+            Self::Text(value) => serializer.serialize_str(value),
+            Self::Image(rows) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("image", rows)?;
+                map.end()
+            }
+            Self::List(values) => values.serialize(serializer),
+        }
+    }
+}
+
+struct AnswerVisitor;
+
+impl<'de> Visitor<'de> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an integer, a string, a list of answers or an image")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Answer, E> {
+        Ok(Answer::from(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Answer, E> {
+        Ok(Answer::from(value))
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<Answer, E> {
+        Ok(Answer::from(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Answer, E> {
+        Ok(Answer::from(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Answer, A::Error> {
+        let mut values = vec![];
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Answer::List(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Answer, A::Error> {
+        let mut rows = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "image" => rows = Some(map.next_value()?),
+                _ => return Err(de::Error::unknown_field(&key, &["image"])),
+            }
+        }
+        rows.map(Answer::Image)
+            .ok_or_else(|| de::Error::missing_field("image"))
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
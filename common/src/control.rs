@@ -0,0 +1,87 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A flag shared between a solver and whoever may want to stop it.
+///
+/// Clones share the same flag, so one can be handed to another thread (a
+/// timeout, a UI) while the solver polls the other.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// How far along a solver is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress<'a> {
+    /// Between 0 and 1, for the whole solve rather than the current phase.
+    pub fraction: f64,
+    pub phase: &'a str,
+}
+
+/// Passed to long running solvers so they can report progress and stop
+/// early when cancelled.
+#[derive(Default)]
+pub struct Control<'a> {
+    token: CancellationToken,
+    on_progress: Option<&'a dyn Fn(Progress<'_>)>,
+}
+
+impl<'a> Control<'a> {
+    #[must_use]
+    pub fn new(token: CancellationToken) -> Self {
+        Self {
+            token,
+            on_progress: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_progress(mut self, on_progress: &'a dyn Fn(Progress<'_>)) -> Self {
+        self.on_progress = Some(on_progress);
+        self
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Reports that the solve is `fraction` complete and currently in
+    /// `phase`. `fraction` is clamped to `0..=1`.
+    pub fn report(&self, phase: &str, fraction: f64) {
+        if let Some(on_progress) = self.on_progress {
+            on_progress(Progress {
+                fraction: fraction.clamp(0.0, 1.0),
+                phase,
+            });
+        }
+    }
+}
+
+impl fmt::Debug for Control<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Control")
+            .field("token", &self.token)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
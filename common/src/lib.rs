@@ -0,0 +1,73 @@
+mod answer;
+mod control;
+
+pub use answer::Answer;
+pub use control::{CancellationToken, Control, Progress};
+
+#[cfg(test)]
+mod tests {
+    use crate::{Answer, CancellationToken, Control, Progress};
+    use std::cell::RefCell;
+
+    #[test]
+    fn answer_integers_compare_across_widths() {
+        assert_eq!(Answer::from(42u64), Answer::from(42i64));
+        assert_eq!(Answer::from(42usize), Answer::from(42isize));
+        assert_eq!(
+            Answer::from(u64::MAX).as_integer(),
+            Some(i128::from(u64::MAX))
+        );
+        assert_ne!(Answer::from(42), Answer::from("42"));
+        assert_eq!(Answer::try_from(42u128), Ok(Answer::from(42)));
+        assert!(Answer::try_from(u128::MAX).is_err());
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::from(-7).to_string(), "-7");
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+        assert_eq!(Answer::from(vec![7usize, 5]).to_string(), "7,5");
+        assert_eq!(Answer::image(["#.", ".#"]).to_string(), "#.\n.#");
+    }
+
+    #[test]
+    fn answer_json_round_trip() {
+        let answers = vec![
+            (Answer::from(11_583_882_601_918i64), "11583882601918"),
+            (Answer::from(u64::MAX), "18446744073709551615"),
+            (Answer::from("VRZGHDFBQ"), "\"VRZGHDFBQ\""),
+            (Answer::from(vec![19usize, 23]), "[19,23]"),
+            (Answer::image(["#.", ".#"]), "{\"image\":[\"#.\",\".#\"]}"),
+        ];
+        for (answer, json) in answers {
+            assert_eq!(serde_json::to_string(&answer).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Answer>(json).unwrap(), answer);
+        }
+    }
+
+    #[test]
+    fn control_reports_and_cancels() {
+        let reports = RefCell::new(vec![]);
+        let on_progress = |progress: Progress<'_>| {
+            reports
+                .borrow_mut()
+                .push((progress.phase.to_string(), progress.fraction));
+        };
+        let token = CancellationToken::new();
+        let control = Control::new(token.clone()).with_progress(&on_progress);
+
+        control.report("rounds", 0.5);
+        control.report("rounds", 1.5);
+        assert!(!control.is_cancelled());
+        token.cancel();
+        assert!(control.is_cancelled());
+        assert_eq!(
+            reports.into_inner(),
+            vec![("rounds".to_string(), 0.5), ("rounds".to_string(), 1.0)]
+        );
+
+        let control = Control::default();
+        control.report("ignored", 0.5);
+        assert!(!control.is_cancelled());
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{group_total, Error};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// What a single elf carries, numbered from 0 in input order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ElfInventory {
+    pub elf: usize,
+    pub items: usize,
+    pub calories: u64,
+}
+
+/// Elves whose totals fall in `start..=end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+    pub start: u64,
+    pub end: u64,
+    pub count: usize,
+}
+
+/// Elves carrying exactly the same number of calories.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Tie {
+    pub calories: u64,
+    pub elves: Vec<usize>,
+}
+
+/// Per-elf totals and statistics over them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalorieLedger {
+    elves: Vec<ElfInventory>,
+    /// Every total, smallest first.
+    sorted: Vec<u64>,
+}
+
+impl CalorieLedger {
+    /// # Errors
+    ///
+    /// Will return `Err` if an elf carries more calories than a `u64`
+    /// holds.
+    pub fn new(groups: &[Vec<u64>]) -> Result<Self, Error> {
+        let elves = groups
+            .iter()
+            .enumerate()
+            .map(|(elf, items)| {
+                Ok(ElfInventory {
+                    elf,
+                    items: items.len(),
+                    calories: group_total(elf, items)?,
+                })
+            })
+            .collect::<Result<Vec<ElfInventory>, Error>>()?;
+        let mut sorted: Vec<u64> = elves.iter().map(|elf| elf.calories).collect();
+        sorted.sort_unstable();
+
+        Ok(Self { elves, sorted })
+    }
+
+    /// Every elf, in input order.
+    #[must_use]
+    pub fn elves(&self) -> &[ElfInventory] {
+        &self.elves
+    }
+
+    #[must_use]
+    pub fn get(&self, elf: usize) -> Option<&ElfInventory> {
+        self.elves.get(elf)
+    }
+
+    /// 1 for the elf carrying the most calories. Tied elves share a rank
+    /// and the next one is skipped, so two elves tied first are followed by
+    /// a third.
+    #[must_use]
+    pub fn rank(&self, elf: usize) -> Option<usize> {
+        let calories = self.get(elf)?.calories;
+        let carrying_more = self.sorted.len() - self.sorted.partition_point(|&c| c <= calories);
+
+        Some(carrying_more + 1)
+    }
+
+    /// Every elf, most calories first, with its rank.
+    #[must_use]
+    pub fn ranked(&self) -> Vec<(usize, &ElfInventory)> {
+        let mut elves: Vec<&ElfInventory> = self.elves.iter().collect();
+        elves.sort_by(|a, b| b.calories.cmp(&a.calories).then(a.elf.cmp(&b.elf)));
+
+        elves
+            .into_iter()
+            .map(|elf| (self.rank(elf.elf).unwrap_or_default(), elf))
+            .collect()
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean(&self) -> Option<f64> {
+        if self.sorted.is_empty() {
+            return None;
+        }
+        let total: u128 = self.sorted.iter().map(|&c| u128::from(c)).sum();
+
+        Some(total as f64 / self.sorted.len() as f64)
+    }
+
+    /// The middle total, or the mean of the two middle ones.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn median(&self) -> Option<f64> {
+        let middle = self.sorted.len() / 2;
+        match self.sorted.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(self.sorted[middle] as f64),
+            _ => Some(f64::midpoint(
+                self.sorted[middle - 1] as f64,
+                self.sorted[middle] as f64,
+            )),
+        }
+    }
+
+    /// The smallest total that at least `percent` percent of the elves carry
+    /// no more than (nearest-rank method). `percent` is clamped to `0..=100`.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn percentile(&self, percent: f64) -> Option<u64> {
+        let rank = (percent.clamp(0.0, 100.0) / 100.0 * self.sorted.len() as f64).ceil() as usize;
+
+        self.sorted.get(rank.saturating_sub(1)).copied()
+    }
+
+    /// Splits the range of totals into `buckets` buckets of equal width and
+    /// counts the elves in each. The last bucket ends at `u64::MAX` at the
+    /// latest.
+    #[must_use]
+    pub fn histogram(&self, buckets: usize) -> Vec<Bucket> {
+        let (Some(&min), Some(&max)) = (self.sorted.first(), self.sorted.last()) else {
+            return vec![];
+        };
+        let Ok(buckets) = u128::try_from(buckets) else {
+            return vec![];
+        };
+        if buckets == 0 {
+            return vec![];
+        }
+        // The whole range of a `u64` is one more than it holds.
+        let width = (u128::from(max) - u128::from(min) + 1).div_ceil(buckets);
+
+        (0..buckets)
+            .map_while(|bucket| u64::try_from(u128::from(min) + bucket * width).ok())
+            .take_while(|&start| start <= max)
+            .map(|start| {
+                let end = u64::try_from(u128::from(start) + width - 1).unwrap_or(u64::MAX);
+                let count = self.sorted.partition_point(|&c| c <= end)
+                    - self.sorted.partition_point(|&c| c < start);
+                Bucket { start, end, count }
+            })
+            .collect()
+    }
+
+    /// The elves whose group has no items at all.
+    #[must_use]
+    pub fn empty(&self) -> Vec<usize> {
+        self.elves
+            .iter()
+            .filter(|elf| elf.items == 0)
+            .map(|elf| elf.elf)
+            .collect()
+    }
+
+    /// Every total carried by more than one elf, largest first.
+    #[must_use]
+    pub fn ties(&self) -> Vec<Tie> {
+        let mut by_total: BTreeMap<u64, Vec<usize>> = BTreeMap::new();
+        for elf in &self.elves {
+            by_total.entry(elf.calories).or_default().push(elf.elf);
+        }
+
+        by_total
+            .into_iter()
+            .rev()
+            .filter(|(_, elves)| elves.len() > 1)
+            .map(|(calories, elves)| Tie { calories, elves })
+            .collect()
+    }
+}
@@ -1,64 +1,571 @@
-use nom::{
-    character::complete::{self, multispace1, newline},
-    multi::separated_list1,
-    IResult as NomResult,
+mod ledger;
+
+pub use ledger::{Bucket, CalorieLedger, ElfInventory, Tie};
+
+use common::Answer;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fs::File,
+    io::{BufRead, BufReader, Read},
 };
 
 #[derive(Debug)]
 pub enum Error {
     IO(std::io::Error),
-    Nom(String),
+    /// A line that is neither blank nor a calorie count, with its 1-based
+    /// line number.
+    InvalidLine(usize, String),
+    Json(serde_json::Error),
+    /// The calories of elf `elf`, numbered from 0, add up to more than a
+    /// `u64` holds. `line` is the 1-based line of the item that overflowed,
+    /// when the input was read a line at a time.
+    CalorieOverflow {
+        elf: usize,
+        line: Option<usize>,
+    },
+}
+
+/// The ways calorie groups can be written down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The puzzle format: one number per line, groups separated by blank
+    /// lines.
+    Text,
+    /// `elf,calories` rows, optionally under a header. Rows of the same elf
+    /// are grouped together and an empty `calories` field stands for an elf
+    /// carrying nothing.
+    Csv,
+    /// An array of arrays of numbers.
+    Json,
+}
+
+impl Format {
+    /// Guesses the format of `input` from its first non-blank line.
+    #[must_use]
+    pub fn detect(input: &str) -> Self {
+        let first_line = input.lines().map(str::trim).find(|line| !line.is_empty());
+        match first_line {
+            Some(line) if line.starts_with('[') => Self::Json,
+            Some(line) if line.contains(',') => Self::Csv,
+            _ => Self::Text,
+        }
+    }
+}
+
+enum TextEvent {
+    /// An item and its 1-based line number.
+    Item(u64, usize),
+    /// The current group, which may be empty, is complete.
+    EndOfGroup,
+}
+
+/// Splits the lines of the text format into groups.
+///
+/// Leading and trailing blank lines are ignored. Between two groups every
+/// blank line after the first stands for an elf carrying nothing, so such
+/// elves show up as empty groups rather than disappearing.
+#[derive(Default)]
+struct TextGroups {
+    in_group: bool,
+    seen_group: bool,
+    blank_lines: usize,
+}
+
+impl TextGroups {
+    fn line(
+        &mut self,
+        line_number: usize,
+        line: &str,
+        on_event: &mut impl FnMut(TextEvent) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line.trim().is_empty() {
+            if self.in_group {
+                on_event(TextEvent::EndOfGroup)?;
+                self.in_group = false;
+            }
+            self.blank_lines += 1;
+            return Ok(());
+        }
+
+        let calories = line
+            .trim()
+            .parse()
+            .map_err(|_| Error::InvalidLine(line_number, line.to_string()))?;
+        if !self.in_group {
+            if self.seen_group {
+                for _ in 1..self.blank_lines {
+                    on_event(TextEvent::EndOfGroup)?;
+                }
+            }
+            self.in_group = true;
+            self.seen_group = true;
+            self.blank_lines = 0;
+        }
+        on_event(TextEvent::Item(calories, line_number))
+    }
+
+    fn finish(
+        self,
+        on_event: &mut impl FnMut(TextEvent) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        if self.in_group {
+            on_event(TextEvent::EndOfGroup)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_text(input: &str) -> Result<Vec<Vec<u64>>, Error> {
+    let mut groups = vec![];
+    let mut current = vec![];
+    let mut on_event = |event| {
+        match event {
+            TextEvent::Item(calories, _) => current.push(calories),
+            TextEvent::EndOfGroup => groups.push(std::mem::take(&mut current)),
+        }
+        Ok(())
+    };
+
+    let mut splitter = TextGroups::default();
+    for (index, line) in input.lines().enumerate() {
+        splitter.line(index + 1, line, &mut on_event)?;
+    }
+    splitter.finish(&mut on_event)?;
+
+    Ok(groups)
+}
+
+fn parse_csv(input: &str) -> Result<Vec<Vec<u64>>, Error> {
+    let mut groups: Vec<Vec<u64>> = vec![];
+    let mut elves: HashMap<&str, usize> = HashMap::new();
+    let mut seen_row = false;
+
+    for (index, line) in input.lines().enumerate() {
+        let invalid = || Error::InvalidLine(index + 1, line.to_string());
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (elf, calories) = line.split_once(',').ok_or_else(invalid)?;
+        let (elf, calories) = (elf.trim(), calories.trim());
+        let is_header = !seen_row && calories.eq_ignore_ascii_case("calories");
+        seen_row = true;
+        if is_header {
+            continue;
+        }
+
+        let group = *elves.entry(elf).or_insert_with(|| {
+            groups.push(vec![]);
+            groups.len() - 1
+        });
+        if !calories.is_empty() {
+            groups[group].push(calories.parse().map_err(|_| invalid())?);
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Parses `input` into the calories of every item, grouped by elf, guessing
+/// its format with [`Format::detect`].
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not valid in its format.
+pub fn parse_groups(input: &str) -> Result<Vec<Vec<u64>>, Error> {
+    parse_groups_as(input, Format::detect(input))
+}
+
+/// Parses `input`, written in `format`, into the calories of every item,
+/// grouped by elf.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not valid in `format`.
+pub fn parse_groups_as(input: &str, format: Format) -> Result<Vec<Vec<u64>>, Error> {
+    match format {
+        Format::Text => parse_text(input),
+        Format::Csv => parse_csv(input),
+        Format::Json => serde_json::from_str(input).map_err(Error::Json),
+    }
+}
+
+/// The calories carried by a single elf, numbered from 0 in input order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElfTotal {
+    pub elf: usize,
+    pub calories: u64,
+}
+
+impl ElfTotal {
+    /// Orders by calories, then favours the elf that came first.
+    const fn key(self) -> (u64, Reverse<usize>) {
+        (self.calories, Reverse(self.elf))
+    }
+}
+
+/// The `k` largest totals offered so far.
+struct TopCalories {
+    k: usize,
+    // A min-heap, so the weakest of the current top `k` is the one to evict.
+    heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
 }
 
-fn parse_input(input: &str) -> NomResult<&str, Vec<Vec<u64>>> {
-    let (input, groups) =
-        separated_list1(multispace1, separated_list1(newline, complete::u64))(input)?;
+impl TopCalories {
+    fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    fn offer(&mut self, total: ElfTotal) {
+        self.heap.push(Reverse(total.key()));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    fn into_sorted_vec(self) -> Vec<ElfTotal> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(elf)))| ElfTotal { elf, calories })
+            .collect()
+    }
+}
+
+/// Returns the `k` elves carrying the most calories, most first.
+///
+/// Calorie groups are read from `reader`, in the text format, a line at a
+/// time and only `k` totals are ever kept, so the input can be larger than
+/// memory. Ties go to the elf that came first.
+///
+/// # Errors
+///
+/// Will return `Err` if `reader` fails, contains a line that is neither
+/// blank nor a number or an elf carries more calories than a `u64` holds.
+pub fn top_calories<R: BufRead>(mut reader: R, k: usize) -> Result<Vec<ElfTotal>, Error> {
+    let mut top = TopCalories::new(k);
+    let mut elf = 0;
+    let mut calories: u64 = 0;
+    let mut on_event = |event| {
+        match event {
+            TextEvent::Item(item, line) => {
+                calories = calories.checked_add(item).ok_or(Error::CalorieOverflow {
+                    elf,
+                    line: Some(line),
+                })?;
+            }
+            TextEvent::EndOfGroup => {
+                top.offer(ElfTotal { elf, calories });
+                elf += 1;
+                calories = 0;
+            }
+        }
+        Ok(())
+    };
+
+    let mut splitter = TextGroups::default();
+    let mut line = String::new();
+    let mut line_number = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(Error::IO)? == 0 {
+            break;
+        }
+        line_number += 1;
+        splitter.line(line_number, &line, &mut on_event)?;
+    }
+    splitter.finish(&mut on_event)?;
 
-    Ok((input, groups))
+    Ok(top.into_sorted_vec())
 }
 
 /// Finds read file `path` and calculates the sum of all calories for
 /// each person, then return the sum based on `window` greatest amounts
 ///
+/// The format is guessed like [`calc_max_calories_window_from_str`] does.
+/// Text files are streamed through [`top_calories`], the other formats are
+/// read whole.
+///
+/// # Errors
+///
+/// Will return `Err` if `path` does not exist, the user does not have
+/// permission to read it or it is not valid in its format.
+pub fn calc_max_calories_window(path: &str, window: usize) -> Result<Answer, Error> {
+    let mut reader = BufReader::new(File::open(path).map_err(Error::IO)?);
+
+    // Read up to the first non-blank line to guess the format.
+    let mut head = String::new();
+    while head.trim().is_empty() {
+        if reader.read_line(&mut head).map_err(Error::IO)? == 0 {
+            break;
+        }
+    }
+    if Format::detect(&head) != Format::Text {
+        reader.read_to_string(&mut head).map_err(Error::IO)?;
+        return calc_max_calories_window_from_str(&head, window);
+    }
+
+    let top = top_calories(head.as_bytes().chain(reader), window)?;
+
+    Ok(window_total(&top))
+}
+
+/// Solves [`calc_max_calories_window`] from the puzzle text in `input`.
+///
 /// # Errors
 ///
-/// Will return `Err` if `path` does not exist or the user does not have
-/// permission to read it.
-pub fn calc_max_calories_window(path: &str, window: usize) -> Result<u64, Error> {
-    let input = std::fs::read_to_string(path).map_err(Error::IO)?;
-    let (_, groups) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn calc_max_calories_window_from_str(input: &str, window: usize) -> Result<Answer, Error> {
+    let mut top = TopCalories::new(window);
+    for (elf, group) in parse_groups(input)?.iter().enumerate() {
+        let calories = group_total(elf, group)?;
+        top.offer(ElfTotal { elf, calories });
+    }
+
+    Ok(window_total(&top.into_sorted_vec()))
+}
 
-    let mut max: Vec<u64> = groups.iter().map(|g| g.iter().sum()).collect();
-    max.sort_unstable();
+/// The calories of all the `items` of elf `elf`.
+fn group_total(elf: usize, items: &[u64]) -> Result<u64, Error> {
+    items
+        .iter()
+        .try_fold(0, |total: u64, &item| total.checked_add(item))
+        .ok_or(Error::CalorieOverflow { elf, line: None })
+}
 
-    Ok(max.into_iter().rev().take(window).sum())
+/// The calories of `top` together, which may not fit in a `u64`.
+fn window_total(top: &[ElfTotal]) -> Answer {
+    Answer::from(top.iter().map(|elf| i128::from(elf.calories)).sum::<i128>())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::calc_max_calories_window;
+    use crate::{
+        calc_max_calories_window, calc_max_calories_window_from_str, parse_groups, parse_groups_as,
+        top_calories, Bucket, CalorieLedger, ElfTotal, Error, Format, Tie,
+    };
+    use common::Answer;
+    use std::{fs::File, io::BufReader};
 
     #[test]
     fn day_one_part_one_example() {
         let result = calc_max_calories_window("example.txt", 1).unwrap();
-        assert_eq!(result, 24_000);
+        assert_eq!(result, Answer::from(24_000));
     }
 
     #[test]
     fn day_one_part_one_data() {
         let result = calc_max_calories_window("data.txt", 1).unwrap();
-        assert_eq!(result, 72_511);
+        assert_eq!(result, Answer::from(72_511));
     }
 
     #[test]
     fn day_one_part_two_example() {
         let result = calc_max_calories_window("example.txt", 3).unwrap();
-        assert_eq!(result, 45_000);
+        assert_eq!(result, Answer::from(45_000));
     }
 
     #[test]
     fn day_one_part_two_data() {
         let result = calc_max_calories_window("data.txt", 3).unwrap();
-        assert_eq!(result, 212_117);
+        assert_eq!(result, Answer::from(212_117));
+    }
+
+    #[test]
+    fn day_one_top_calories_example() {
+        let file = File::open("example.txt").unwrap();
+        let result = top_calories(BufReader::new(file), 3).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                ElfTotal {
+                    elf: 3,
+                    calories: 24_000
+                },
+                ElfTotal {
+                    elf: 2,
+                    calories: 11_000
+                },
+                ElfTotal {
+                    elf: 4,
+                    calories: 10_000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn day_one_top_calories_ties_and_errors() {
+        let input = "\n5\n\n\n2\n3\r\n\r\n1\n";
+        let result = top_calories(input.as_bytes(), 5).unwrap();
+        let elves: Vec<(usize, u64)> = result.iter().map(|e| (e.elf, e.calories)).collect();
+        assert_eq!(elves, vec![(0, 5), (2, 5), (3, 1), (1, 0)]);
+        assert!(top_calories(input.as_bytes(), 0).unwrap().is_empty());
+
+        let result = top_calories(&b"1\n\nfour\n"[..], 1);
+        assert!(matches!(result, Err(Error::InvalidLine(3, line)) if line == "four"));
+
+        let input = format!("1\n\n{}\n2\n\n{}\n", u64::MAX - 1, u64::MAX);
+        let result = top_calories(input.as_bytes(), 1);
+        assert!(matches!(
+            result,
+            Err(Error::CalorieOverflow {
+                elf: 1,
+                line: Some(4)
+            })
+        ));
+        assert!(matches!(
+            calc_max_calories_window_from_str(&format!("[[{}, 2]]", u64::MAX), 1),
+            Err(Error::CalorieOverflow { elf: 0, line: None })
+        ));
+        let input = format!("{}\n\n{}\n", u64::MAX, u64::MAX);
+        assert_eq!(
+            calc_max_calories_window_from_str(&input, 2).unwrap(),
+            Answer::Integer(2 * i128::from(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn day_one_calorie_ledger() {
+        let groups = parse_groups("1000\n2000\n\n4000\n\n3000\n\n500\n2500\n\n100\n").unwrap();
+        let ledger = CalorieLedger::new(&groups).unwrap();
+
+        let elves: Vec<(usize, u64)> = ledger
+            .elves()
+            .iter()
+            .map(|e| (e.items, e.calories))
+            .collect();
+        assert_eq!(
+            elves,
+            vec![(2, 3000), (1, 4000), (1, 3000), (2, 3000), (1, 100)]
+        );
+        assert_eq!(ledger.rank(1), Some(1));
+        assert_eq!(ledger.rank(2), Some(2));
+        assert_eq!(ledger.rank(4), Some(5));
+        assert_eq!(ledger.rank(5), None);
+        assert_eq!(ledger.mean(), Some(2620.0));
+        assert_eq!(ledger.median(), Some(3000.0));
+        assert_eq!(ledger.percentile(0.0), Some(100));
+        assert_eq!(ledger.percentile(20.0), Some(100));
+        assert_eq!(ledger.percentile(90.0), Some(4000));
+        assert_eq!(
+            ledger.histogram(2),
+            vec![
+                Bucket {
+                    start: 100,
+                    end: 2050,
+                    count: 1
+                },
+                Bucket {
+                    start: 2051,
+                    end: 4001,
+                    count: 4
+                },
+            ]
+        );
+        assert_eq!(
+            ledger.ties(),
+            vec![Tie {
+                calories: 3000,
+                elves: vec![0, 2, 3]
+            }]
+        );
+        assert!(ledger.empty().is_empty());
+        assert_eq!(
+            CalorieLedger::new(&[vec![1], vec![]]).unwrap().empty(),
+            vec![1]
+        );
+        assert!(CalorieLedger::new(&[]).unwrap().median().is_none());
+
+        assert!(matches!(
+            CalorieLedger::new(&[vec![1], vec![u64::MAX, 1]]),
+            Err(Error::CalorieOverflow { elf: 1, line: None })
+        ));
+        let extremes = CalorieLedger::new(&[vec![0], vec![u64::MAX], vec![u64::MAX - 1]]).unwrap();
+        assert_eq!(
+            extremes.histogram(2),
+            vec![
+                Bucket {
+                    start: 0,
+                    end: u64::MAX / 2,
+                    count: 1
+                },
+                Bucket {
+                    start: u64::MAX / 2 + 1,
+                    end: u64::MAX,
+                    count: 2
+                },
+            ]
+        );
+        let top = CalorieLedger::new(&[vec![u64::MAX - 2], vec![u64::MAX]]).unwrap();
+        assert_eq!(
+            top.histogram(2),
+            vec![
+                Bucket {
+                    start: u64::MAX - 2,
+                    end: u64::MAX - 1,
+                    count: 1
+                },
+                Bucket {
+                    start: u64::MAX,
+                    end: u64::MAX,
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn day_one_input_formats() {
+        let text = "\r\n\r\n1000\r\n2000\r\n\r\n\r\n\r\n3000\r\n\r\n\r\n";
+        let csv = "elf,calories\nalice,1000\nbob,\ncarol,3000\nalice,2000\n";
+        let json = "[[1000, 2000], [], [3000]]";
+        let expected = vec![vec![1000, 2000], vec![], vec![], vec![3000]];
+
+        assert_eq!(Format::detect(text), Format::Text);
+        assert_eq!(parse_groups(text).unwrap(), expected);
+        assert_eq!(Format::detect(csv), Format::Csv);
+        assert_eq!(
+            parse_groups(csv).unwrap(),
+            [&expected[..1], &expected[2..]].concat()
+        );
+        assert_eq!(Format::detect(json), Format::Json);
+        assert_eq!(
+            parse_groups(json).unwrap(),
+            [&expected[..1], &expected[2..]].concat()
+        );
+
+        let top = top_calories(text.as_bytes(), 4).unwrap();
+        let elves: Vec<(usize, u64)> = top.iter().map(|e| (e.elf, e.calories)).collect();
+        assert_eq!(elves, vec![(0, 3000), (3, 3000), (1, 0), (2, 0)]);
+        assert_eq!(
+            calc_max_calories_window_from_str(csv, 1).unwrap(),
+            Answer::from(3000)
+        );
+
+        assert!(matches!(
+            parse_groups_as("elf,calories\nalice,lots\n", Format::Csv),
+            Err(Error::InvalidLine(2, _))
+        ));
+        assert!(matches!(parse_groups("[[1], [-2]]"), Err(Error::Json(_))));
+
+        let directory = std::env::temp_dir().join(format!("day_01_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        for (name, input) in [("text.txt", text), ("c.csv", csv), ("c.json", json)] {
+            let path = directory.join(name);
+            std::fs::write(&path, input).unwrap();
+            let path = path.to_str().unwrap();
+            assert_eq!(
+                calc_max_calories_window(path, 2).unwrap(),
+                calc_max_calories_window_from_str(input, 2).unwrap(),
+                "{name}"
+            );
+        }
+        std::fs::remove_dir_all(directory).unwrap();
     }
 }
@@ -0,0 +1,121 @@
+use crate::{Choice, Game, Match, Outcome, SymbolMap};
+
+/// How a single round of a guide played out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundBreakdown {
+    /// 1-based line of the round in the guide.
+    pub line: usize,
+    pub opponent: Choice,
+    pub player: Choice,
+    pub outcome: Outcome,
+    pub shape_points: u64,
+    pub outcome_points: u64,
+    /// The player's score after this round.
+    pub running_total: u64,
+}
+
+/// How a guide compares to the best possible play against the same
+/// opponent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Optimization {
+    pub rounds: usize,
+    pub guide_score: u64,
+    pub best_score: u64,
+    /// Rounds where another weapon would have scored more.
+    pub suboptimal_rounds: usize,
+}
+
+impl Optimization {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn suboptimal_fraction(&self) -> f64 {
+        if self.rounds == 0 {
+            0.0
+        } else {
+            self.suboptimal_rounds as f64 / self.rounds as f64
+        }
+    }
+}
+
+impl Game {
+    /// Plays every round of `guide` like [`Game::score_guide`] does and
+    /// returns how each of them was scored.
+    #[must_use]
+    pub fn breakdown(&self, guide: &str, match_type: &Match) -> Vec<RoundBreakdown> {
+        self.breakdown_with(guide, self.symbols(), match_type)
+    }
+
+    /// Same as [`Game::breakdown`], decoding the guide with `symbols`.
+    #[must_use]
+    pub fn breakdown_with(
+        &self,
+        guide: &str,
+        symbols: &SymbolMap,
+        match_type: &Match,
+    ) -> Vec<RoundBreakdown> {
+        let mut running_total = 0;
+
+        guide
+            .lines()
+            .enumerate()
+            .filter_map(|(index, round)| {
+                let (opponent, player) = self.decode_round(round, symbols, *match_type)?;
+                let outcome = self.outcome(opponent, player);
+                let shape_points = self.weapons()[player.0].score;
+                let outcome_points = self.outcome_score(outcome);
+                running_total += shape_points + outcome_points;
+
+                Some(RoundBreakdown {
+                    line: index + 1,
+                    opponent,
+                    player,
+                    outcome,
+                    shape_points,
+                    outcome_points,
+                    running_total,
+                })
+            })
+            .collect()
+    }
+
+    /// The weapon scoring the most against `opponent`, the first defined
+    /// on ties.
+    #[must_use]
+    pub fn best_choice(&self, opponent: Choice) -> Choice {
+        (0..self.weapons().len())
+            .map(Choice)
+            .min_by_key(|&player| std::cmp::Reverse(self.score(opponent, player)))
+            .unwrap_or(opponent)
+    }
+
+    /// The highest score reachable against `opponents`.
+    #[must_use]
+    pub fn best_score(&self, opponents: &[Choice]) -> u64 {
+        opponents
+            .iter()
+            .map(|&opponent| self.score(opponent, self.best_choice(opponent)))
+            .sum()
+    }
+
+    /// Compares the guide's plays with the best response to every one of
+    /// the opponent's weapons.
+    #[must_use]
+    pub fn optimize(&self, guide: &str, match_type: &Match) -> Optimization {
+        let rounds = self.breakdown(guide, match_type);
+        let opponents: Vec<Choice> = rounds.iter().map(|round| round.opponent).collect();
+        let suboptimal_rounds = rounds
+            .iter()
+            .filter(|round| {
+                let best = self.score(round.opponent, self.best_choice(round.opponent));
+                round.shape_points + round.outcome_points < best
+            })
+            .count();
+
+        Optimization {
+            rounds: rounds.len(),
+            guide_score: rounds.last().map_or(0, |round| round.running_total),
+            best_score: self.best_score(&opponents),
+            suboptimal_rounds,
+        }
+    }
+}
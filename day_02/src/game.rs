@@ -0,0 +1,434 @@
+use crate::{Error, Match, SymbolMap};
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag},
+    character::complete::{self, space0, space1},
+    combinator::{eof, map, value},
+    sequence::{terminated, tuple},
+    IResult as NomResult,
+};
+use std::{collections::HashSet, fs};
+
+const ROCK_PAPER_SCISSORS: &str = include_str!("../rules/rock_paper_scissors.txt");
+
+/// A weapon of a [`Game`], by its index in [`Game::weapons`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Choice(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Self; 3] = [Self::Lose, Self::Draw, Self::Win];
+
+    const fn index(self) -> usize {
+        match self {
+            Self::Lose => 0,
+            Self::Draw => 1,
+            Self::Win => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Weapon {
+    pub name: String,
+    pub score: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OutcomeRule {
+    score: u64,
+    symbol: String,
+}
+
+enum Rule<'a> {
+    /// A weapon with its opponent and player symbols.
+    Weapon(Weapon, &'a str, &'a str),
+    Outcome(Outcome, OutcomeRule),
+    Beats(&'a str, &'a str),
+}
+
+/// One way of reading a strategy guide and the score it leads to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpretation {
+    pub match_type: Match,
+    pub symbols: SymbolMap,
+    /// What the second column means, such as `X=rock Y=paper Z=scissors`.
+    pub description: String,
+    pub score: u64,
+}
+
+/// A rock-paper-scissors-like game: its weapons, which of them beats
+/// which, and what every weapon and outcome scores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    weapons: Vec<Weapon>,
+    /// `beats[a][b]` is whether weapon `a` beats weapon `b`.
+    beats: Vec<Vec<bool>>,
+    outcome_scores: [u64; 3],
+    /// The symbols given in the rules.
+    symbols: SymbolMap,
+}
+
+fn parse_word(input: &str) -> NomResult<&str, &str> {
+    is_not(" \t")(input)
+}
+
+fn parse_weapon(input: &str) -> NomResult<&str, Rule<'_>> {
+    // weapon rock 1 A X
+    let (input, (_, _, name, _, score, _, opponent_symbol, _, player_symbol)) = tuple((
+        tag("weapon"),
+        space1,
+        parse_word,
+        space1,
+        complete::u64,
+        space1,
+        parse_word,
+        space1,
+        parse_word,
+    ))(input)?;
+
+    Ok((
+        input,
+        Rule::Weapon(
+            Weapon {
+                name: name.to_string(),
+                score,
+            },
+            opponent_symbol,
+            player_symbol,
+        ),
+    ))
+}
+
+fn parse_outcome(input: &str) -> NomResult<&str, Rule<'_>> {
+    // outcome win 6 Z
+    let (input, (_, _, outcome, _, score, _, symbol)) = tuple((
+        tag("outcome"),
+        space1,
+        alt((
+            value(Outcome::Lose, tag("lose")),
+            value(Outcome::Draw, tag("draw")),
+            value(Outcome::Win, tag("win")),
+        )),
+        space1,
+        complete::u64,
+        space1,
+        parse_word,
+    ))(input)?;
+
+    Ok((
+        input,
+        Rule::Outcome(
+            outcome,
+            OutcomeRule {
+                score,
+                symbol: symbol.to_string(),
+            },
+        ),
+    ))
+}
+
+fn parse_beats(input: &str) -> NomResult<&str, Rule<'_>> {
+    // rock beats scissors
+    map(
+        tuple((parse_word, space1, tag("beats"), space1, parse_word)),
+        |(winner, _, _, _, loser)| Rule::Beats(winner, loser),
+    )(input)
+}
+
+fn parse_rule(input: &str) -> NomResult<&str, Rule<'_>> {
+    terminated(
+        alt((parse_weapon, parse_outcome, parse_beats)),
+        tuple((space0, eof)),
+    )(input)
+}
+
+impl Game {
+    /// The game from the puzzle.
+    #[must_use]
+    pub fn rock_paper_scissors() -> Self {
+        Self::parse(ROCK_PAPER_SCISSORS)
+            .unwrap_or_else(|_| unreachable!("the built-in rules are valid"))
+    }
+
+    /// Reads a game from a rules file, see [`Game::parse`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `path` cannot be read or does not hold valid
+    /// rules.
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let config = fs::read_to_string(path).map_err(Error::IO)?;
+
+        Self::parse(&config)
+    }
+
+    /// Parses a game from its rules, one per line:
+    ///
+    /// ```text
+    /// weapon <name> <score> <opponent symbol> <player symbol>
+    /// outcome <lose|draw|win> <score> <symbol>
+    /// <weapon> beats <weapon>
+    /// ```
+    ///
+    /// Blank lines and lines starting with `#` are ignored. The game must
+    /// have an odd number of weapons, each beating exactly half of the
+    /// others, and a score for every outcome.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if a line is not a rule or the rules do not make a
+    /// valid game.
+    pub fn parse(config: &str) -> Result<Self, Error> {
+        let mut weapons: Vec<(Weapon, &str, &str)> = vec![];
+        let mut outcomes: [Option<OutcomeRule>; 3] = [None, None, None];
+        let mut beats: Vec<(&str, &str)> = vec![];
+
+        for (index, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (_, rule) =
+                parse_rule(line).map_err(|e| Error::Nom(format!("line {}: {e}", index + 1)))?;
+            match rule {
+                Rule::Weapon(weapon, opponent_symbol, player_symbol) => {
+                    weapons.push((weapon, opponent_symbol, player_symbol));
+                }
+                Rule::Outcome(outcome, rule) => outcomes[outcome.index()] = Some(rule),
+                Rule::Beats(winner, loser) => beats.push((winner, loser)),
+            }
+        }
+
+        Self::new(weapons, outcomes, &beats)
+    }
+
+    fn new(
+        weapons: Vec<(Weapon, &str, &str)>,
+        outcomes: [Option<OutcomeRule>; 3],
+        beats: &[(&str, &str)],
+    ) -> Result<Self, Error> {
+        let invalid = |message: String| Err(Error::InvalidRules(message));
+
+        if weapons.len().is_multiple_of(2) {
+            return invalid(format!(
+                "a fair game needs an odd number of weapons, not {}",
+                weapons.len()
+            ));
+        }
+        for (what, symbols) in [
+            (
+                "name",
+                weapons
+                    .iter()
+                    .map(|w| w.0.name.as_str())
+                    .collect::<Vec<_>>(),
+            ),
+            ("opponent symbol", weapons.iter().map(|w| w.1).collect()),
+            ("player symbol", weapons.iter().map(|w| w.2).collect()),
+        ] {
+            let mut seen = HashSet::new();
+            if let Some(duplicate) = symbols.into_iter().find(|symbol| !seen.insert(*symbol)) {
+                return invalid(format!("weapon {what} `{duplicate}` is used twice"));
+            }
+        }
+        let [Some(lose), Some(draw), Some(win)] = outcomes else {
+            return invalid("every outcome needs a score".to_string());
+        };
+
+        let mut symbols = SymbolMap::new()
+            .with_outcome(&lose.symbol, Outcome::Lose)
+            .with_outcome(&draw.symbol, Outcome::Draw)
+            .with_outcome(&win.symbol, Outcome::Win);
+        for (index, (_, opponent_symbol, player_symbol)) in weapons.iter().enumerate() {
+            symbols = symbols
+                .with_opponent(opponent_symbol, Choice(index))
+                .with_player(player_symbol, Choice(index));
+        }
+        let weapons: Vec<Weapon> = weapons.into_iter().map(|(weapon, _, _)| weapon).collect();
+
+        let index_of = |name: &str| {
+            weapons
+                .iter()
+                .position(|weapon| weapon.name == name)
+                .ok_or_else(|| Error::InvalidRules(format!("unknown weapon `{name}`")))
+        };
+        let mut matrix = vec![vec![false; weapons.len()]; weapons.len()];
+        for &(winner, loser) in beats {
+            let (winner, loser) = (index_of(winner)?, index_of(loser)?);
+            if winner == loser {
+                return invalid(format!("`{}` can't beat itself", weapons[winner].name));
+            }
+            matrix[winner][loser] = true;
+        }
+
+        for a in 0..weapons.len() {
+            for b in (a + 1)..weapons.len() {
+                if matrix[a][b] == matrix[b][a] {
+                    return invalid(format!(
+                        "exactly one of `{}` and `{}` must beat the other",
+                        weapons[a].name, weapons[b].name
+                    ));
+                }
+            }
+            let wins = matrix[a].iter().filter(|&&beats| beats).count();
+            if wins != weapons.len() / 2 {
+                return invalid(format!(
+                    "`{}` beats {wins} weapons instead of {}",
+                    weapons[a].name,
+                    weapons.len() / 2
+                ));
+            }
+        }
+
+        Ok(Self {
+            weapons,
+            beats: matrix,
+            outcome_scores: [lose.score, draw.score, win.score],
+            symbols,
+        })
+    }
+
+    #[must_use]
+    pub fn weapons(&self) -> &[Weapon] {
+        &self.weapons
+    }
+
+    #[must_use]
+    pub fn weapon(&self, name: &str) -> Option<Choice> {
+        self.weapons
+            .iter()
+            .position(|weapon| weapon.name == name)
+            .map(Choice)
+    }
+
+    /// How the round ends for the player.
+    #[must_use]
+    pub fn outcome(&self, opponent: Choice, player: Choice) -> Outcome {
+        if self.beats[player.0][opponent.0] {
+            Outcome::Win
+        } else if self.beats[opponent.0][player.0] {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// The weapon that gets the player `outcome` against `opponent`. When
+    /// several do, the one scoring the most (then the first defined) wins.
+    #[must_use]
+    pub fn choice_for(&self, opponent: Choice, outcome: Outcome) -> Choice {
+        (0..self.weapons.len())
+            .map(Choice)
+            .filter(|&player| self.outcome(opponent, player) == outcome)
+            .min_by_key(|&player| std::cmp::Reverse(self.weapons[player.0].score))
+            .unwrap_or(opponent)
+    }
+
+    #[must_use]
+    pub const fn outcome_score(&self, outcome: Outcome) -> u64 {
+        self.outcome_scores[outcome.index()]
+    }
+
+    /// The player's score for a round.
+    #[must_use]
+    pub fn score(&self, opponent: Choice, player: Choice) -> u64 {
+        self.weapons[player.0].score + self.outcome_score(self.outcome(opponent, player))
+    }
+
+    /// The symbols given in the rules.
+    #[must_use]
+    pub const fn symbols(&self) -> &SymbolMap {
+        &self.symbols
+    }
+
+    /// Plays every round of a strategy guide, reading its second column as
+    /// `match_type`, and sums up the player's score. Lines that aren't a
+    /// valid round are skipped.
+    #[must_use]
+    pub fn score_guide(&self, guide: &str, match_type: &Match) -> u64 {
+        self.score_guide_with(guide, &self.symbols, match_type)
+    }
+
+    /// Same as [`Game::score_guide`], decoding the guide with `symbols`.
+    #[must_use]
+    pub fn score_guide_with(&self, guide: &str, symbols: &SymbolMap, match_type: &Match) -> u64 {
+        guide
+            .lines()
+            .filter_map(|round| self.decode_round(round, symbols, *match_type))
+            .map(|(opponent, player)| self.score(opponent, player))
+            .sum()
+    }
+
+    /// The opponent's and the player's weapons for a line of a guide.
+    pub(crate) fn decode_round(
+        &self,
+        round: &str,
+        symbols: &SymbolMap,
+        match_type: Match,
+    ) -> Option<(Choice, Choice)> {
+        let mut columns = round.split_whitespace();
+        let opponent = symbols.opponent(columns.next()?)?;
+        let second = columns.next()?;
+        let player = match match_type {
+            Match::Choice => symbols.player(second)?,
+            Match::Outcome => self.choice_for(opponent, symbols.outcome(second)?),
+        };
+
+        Some((opponent, player))
+    }
+
+    /// Scores `guide` under every way of reading its second column: each
+    /// assignment of the rules' player symbols to distinct weapons (the six
+    /// permutations for rock paper scissors) and then as outcomes.
+    #[must_use]
+    pub fn interpretations(&self, guide: &str) -> Vec<Interpretation> {
+        let player_symbols: Vec<&str> = self.symbols.player_symbols().collect();
+
+        let mut interpretations: Vec<Interpretation> = (0..self.weapons.len())
+            .permutations(player_symbols.len())
+            .map(|weapons| {
+                let mut symbols = self.symbols.clone();
+                let mut description = vec![];
+                for (symbol, weapon) in player_symbols.iter().zip(weapons) {
+                    symbols = symbols.with_player(symbol, Choice(weapon));
+                    description.push(format!("{symbol}={}", self.weapons[weapon].name));
+                }
+                Interpretation {
+                    match_type: Match::Choice,
+                    score: self.score_guide_with(guide, &symbols, &Match::Choice),
+                    symbols,
+                    description: description.join(" "),
+                }
+            })
+            .collect();
+
+        let description = self
+            .symbols
+            .outcome_symbols()
+            .filter_map(|symbol| {
+                let outcome = match self.symbols.outcome(symbol)? {
+                    Outcome::Lose => "lose",
+                    Outcome::Draw => "draw",
+                    Outcome::Win => "win",
+                };
+                Some(format!("{symbol}={outcome}"))
+            })
+            .join(" ");
+        interpretations.push(Interpretation {
+            match_type: Match::Outcome,
+            symbols: self.symbols.clone(),
+            description,
+            score: self.score_guide(guide, &Match::Outcome),
+        });
+
+        interpretations
+    }
+}
@@ -1,171 +1,274 @@
-use std::{fs, io::Error};
+mod analysis;
+mod game;
+mod symbols;
+mod tournament;
 
-enum Choice {
-    Rock,
-    Paper,
-    Scissors,
-}
+pub use analysis::{Optimization, RoundBreakdown};
+pub use game::{Choice, Game, Interpretation, Outcome, Weapon};
+pub use symbols::SymbolMap;
+pub use tournament::{
+    play_match, tournament, Always, FixedGuide, FrequencyCounter, LastMoveBeater, SeededRandom,
+    Standing, Strategy, TournamentResult,
+};
+
+use common::Answer;
+use std::fs;
 
-enum Outcome {
-    Lose,
-    Draw,
-    Win,
+#[derive(Debug)]
+pub enum Error {
+    IO(std::io::Error),
+    Nom(String),
+    InvalidRules(String),
+    InvalidSymbols(String),
 }
 
+/// How to read the second column of the strategy guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Match {
     Choice,
     Outcome,
 }
 
-impl Choice {
-    fn from_char(char: &str) -> Option<Self> {
-        match char {
-            "A" | "X" => Some(Self::Rock),
-            "B" | "Y" => Some(Self::Paper),
-            "C" | "Z" => Some(Self::Scissors),
-            _ => None,
-        }
-    }
-
-    const fn value(&self) -> u64 {
-        match self {
-            Self::Rock => 1,
-            Self::Paper => 2,
-            Self::Scissors => 3,
-        }
-    }
-}
-
-impl Outcome {
-    fn from_char(char: &str) -> Option<Self> {
-        match char {
-            "X" => Some(Self::Lose),
-            "Y" => Some(Self::Draw),
-            "Z" => Some(Self::Win),
-            _ => None,
-        }
-    }
-
-    const fn value(&self) -> u64 {
-        match self {
-            Self::Lose => 0,
-            Self::Draw => 3,
-            Self::Win => 6,
-        }
-    }
-}
+/// Runs each round based on the `match_type` interpretation of the second column
+/// and sums up the score of the whole match.
+///
+/// # Errors
+///
+/// Will return `Err` if `path` does not exist or the user does not have
+/// permission to read it.
+pub fn score_from_match_type(path: &str, match_type: &Match) -> Result<Answer, Error> {
+    let input = fs::read_to_string(path).map_err(Error::IO)?;
 
-const fn outcome_from_choices(opponent: &Choice, player: &Choice) -> Outcome {
-    match player {
-        Choice::Rock => match opponent {
-            Choice::Rock => Outcome::Draw,
-            Choice::Paper => Outcome::Lose,
-            Choice::Scissors => Outcome::Win,
-        },
-        Choice::Paper => match opponent {
-            Choice::Paper => Outcome::Draw,
-            Choice::Scissors => Outcome::Lose,
-            Choice::Rock => Outcome::Win,
-        },
-        Choice::Scissors => match opponent {
-            Choice::Scissors => Outcome::Draw,
-            Choice::Paper => Outcome::Win,
-            Choice::Rock => Outcome::Lose,
-        },
-    }
+    Ok(score_from_match_type_from_str(&input, match_type))
 }
 
-const fn score_from_choices(opponent: &Choice, player: &Choice) -> u64 {
-    let outcome = outcome_from_choices(opponent, player);
+/// Solves [`score_from_match_type`] from the puzzle text in `input`.
+#[must_use]
+pub fn score_from_match_type_from_str(input: &str, match_type: &Match) -> Answer {
+    let score = Game::rock_paper_scissors().score_guide(input, match_type);
 
-    outcome.value() + player.value()
+    Answer::from(score)
 }
 
-const fn choice_from_opponent_and_outcome(opponent: &Choice, outcome: &Outcome) -> Choice {
-    match opponent {
-        Choice::Rock => match outcome {
-            Outcome::Draw => Choice::Rock,
-            Outcome::Lose => Choice::Scissors,
-            Outcome::Win => Choice::Paper,
-        },
-        Choice::Paper => match outcome {
-            Outcome::Draw => Choice::Paper,
-            Outcome::Lose => Choice::Rock,
-            Outcome::Win => Choice::Scissors,
-        },
-        Choice::Scissors => match outcome {
-            Outcome::Draw => Choice::Scissors,
-            Outcome::Lose => Choice::Paper,
-            Outcome::Win => Choice::Rock,
-        },
-    }
-}
-
-const fn score_from_choice_and_outcome(opponent: &Choice, outcome: &Outcome) -> u64 {
-    let player = choice_from_opponent_and_outcome(opponent, outcome);
+/// Same as [`score_from_match_type`], playing by the rules file at
+/// `rules_path` instead of the puzzle's rock paper scissors.
+///
+/// # Errors
+///
+/// Will return `Err` if either file cannot be read or the rules are not
+/// valid.
+pub fn score_with_rules(path: &str, rules_path: &str, match_type: &Match) -> Result<Answer, Error> {
+    let input = fs::read_to_string(path).map_err(Error::IO)?;
+    let game = Game::load(rules_path)?;
 
-    outcome.value() + player.value()
+    Ok(Answer::from(game.score_guide(&input, match_type)))
 }
 
-/// Runs each round based on the `match_type` interpretation of the second column
-/// and sums up the score of the whole match.
+/// Scores the guide at `path` under every interpretation of its second
+/// column, see [`Game::interpretations`].
 ///
 /// # Errors
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn score_from_match_type(path: &str, match_type: &Match) -> Result<u64, Error> {
-    let score: u64 = fs::read_to_string(path)?
-        .lines()
-        .filter_map(|round| {
-            let round: Vec<&str> = round.split_whitespace().take(2).collect();
-            let choice = Choice::from_char(round[0]);
-            let match_type_value = match match_type {
-                Match::Choice => (Choice::from_char(round[1]), None),
-                Match::Outcome => (None, Outcome::from_char(round[1])),
-            };
-            match (choice, match_type_value) {
-                (Some(choice), (Some(player_choice), None)) => {
-                    Some(score_from_choices(&choice, &player_choice))
-                }
-                (Some(choice), (None, Some(round_outcome))) => {
-                    Some(score_from_choice_and_outcome(&choice, &round_outcome))
-                }
-                _ => None,
-            }
-        })
-        .sum();
+pub fn analyze_interpretations(path: &str, game: &Game) -> Result<Vec<Interpretation>, Error> {
+    let input = fs::read_to_string(path).map_err(Error::IO)?;
 
-    Ok(score)
+    Ok(game.interpretations(&input))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{score_from_match_type, Match};
+    use crate::{
+        analyze_interpretations, play_match, score_from_match_type, score_with_rules, tournament,
+        Always, Choice, Error, FixedGuide, FrequencyCounter, Game, LastMoveBeater, Match,
+        Optimization, Outcome, RoundBreakdown, SeededRandom, Strategy, SymbolMap,
+    };
+    use common::Answer;
 
     #[test]
     fn day_two_part_one_example() {
         let result = score_from_match_type("example.txt", &Match::Choice).unwrap();
-        assert_eq!(result, 15);
+        assert_eq!(result, Answer::from(15));
     }
 
     #[test]
     fn day_two_part_one_data() {
         let result = score_from_match_type("data.txt", &Match::Choice).unwrap();
-        assert_eq!(result, 11603);
-        assert!(result < 12149);
+        assert_eq!(result, Answer::from(11603));
+        assert!(result < Answer::from(12149));
     }
 
     #[test]
     fn day_two_part_two_example() {
         let result = score_from_match_type("example.txt", &Match::Outcome).unwrap();
-        assert_eq!(result, 12);
+        assert_eq!(result, Answer::from(12));
     }
 
     #[test]
     fn day_two_part_two_data() {
         let result = score_from_match_type("data.txt", &Match::Outcome).unwrap();
-        assert_eq!(result, 12725);
-        assert!(result > 11915);
+        assert_eq!(result, Answer::from(12725));
+        assert!(result > Answer::from(11915));
+    }
+
+    #[test]
+    fn day_two_rules_files() {
+        let result =
+            score_with_rules("data.txt", "rules/rock_paper_scissors.txt", &Match::Outcome).unwrap();
+        assert_eq!(result, Answer::from(12725));
+
+        let game = Game::load("rules/rock_paper_scissors_lizard_spock.txt").unwrap();
+        let spock = game.weapon("spock").unwrap();
+        let rock = game.weapon("rock").unwrap();
+        assert_eq!(game.outcome(rock, spock), Outcome::Win);
+        assert_eq!(game.outcome(spock, spock), Outcome::Draw);
+        // Paper and lizard both beat spock, lizard scores more.
+        assert_eq!(game.choice_for(spock, Outcome::Win), Choice(3));
+        assert_eq!(
+            game.score_guide("E V\nE Z\nA Y\n", &Match::Choice),
+            1 + 8 + 4
+        );
+        // Scissors and rock both lose to spock, scissors scores more.
+        assert_eq!(game.score_guide("E Z\nE X\n", &Match::Outcome), 10 + 3);
+    }
+
+    #[test]
+    fn day_two_invalid_rules() {
+        let rules = "weapon rock 1 A X\nweapon paper 2 B Y\n";
+        assert!(matches!(Game::parse(rules), Err(Error::InvalidRules(_))));
+
+        let rules = "weapon rock 1 A X\nweapon paper 2 B Y\nweapon scissors 3 C Z\n\
+                     outcome lose 0 X\noutcome draw 3 Y\noutcome win 6 Z\n\
+                     rock beats scissors\npaper beats rock\npaper beats scissors\n";
+        assert!(
+            matches!(Game::parse(rules), Err(Error::InvalidRules(message)) if message.contains("`paper` beats 2"))
+        );
+
+        assert!(matches!(
+            Game::parse("rock smashes scissors"),
+            Err(Error::Nom(_))
+        ));
+    }
+
+    #[test]
+    fn day_two_symbol_maps() {
+        let game = Game::rock_paper_scissors();
+        let symbols = SymbolMap::parse(
+            "opponent R rock\nopponent P paper\nopponent S scissors\n\
+             player r rock\nplayer p paper\nplayer s scissors\n",
+            &game,
+        )
+        .unwrap();
+        assert_eq!(
+            game.score_guide_with("P r\nR p\nS s\n", &symbols, &Match::Choice),
+            15
+        );
+        assert!(matches!(
+            SymbolMap::parse("player X lizard", &game),
+            Err(Error::InvalidSymbols(message)) if message == "line 1: unknown weapon `lizard`"
+        ));
+
+        let interpretations = analyze_interpretations("example.txt", &game).unwrap();
+        let scores: Vec<(&str, u64)> = interpretations
+            .iter()
+            .map(|i| (i.description.as_str(), i.score))
+            .collect();
+        assert_eq!(
+            scores,
+            vec![
+                ("X=rock Y=paper Z=scissors", 15),
+                ("X=rock Y=scissors Z=paper", 6),
+                ("X=paper Y=rock Z=scissors", 15),
+                ("X=paper Y=scissors Z=rock", 15),
+                ("X=scissors Y=rock Z=paper", 15),
+                ("X=scissors Y=paper Z=rock", 24),
+                ("X=lose Y=draw Z=win", 12),
+            ]
+        );
+        assert_eq!(interpretations[6].match_type, Match::Outcome);
+    }
+
+    #[test]
+    fn day_two_breakdown_and_optimizer() {
+        let game = Game::rock_paper_scissors();
+        let guide = "A Y\nB X\n\nC Z\n";
+        let rock = game.weapon("rock").unwrap();
+        let paper = game.weapon("paper").unwrap();
+
+        let rounds = game.breakdown(guide, &Match::Choice);
+        assert_eq!(rounds.len(), 3);
+        assert_eq!(
+            rounds[1],
+            RoundBreakdown {
+                line: 2,
+                opponent: paper,
+                player: rock,
+                outcome: Outcome::Lose,
+                shape_points: 1,
+                outcome_points: 0,
+                running_total: 9,
+            }
+        );
+        assert_eq!(rounds[2].line, 4);
+        assert_eq!(rounds[2].running_total, 15);
+
+        let optimization = game.optimize(guide, &Match::Choice);
+        assert_eq!(
+            optimization,
+            Optimization {
+                rounds: 3,
+                guide_score: 15,
+                best_score: 8 + 9 + 7,
+                suboptimal_rounds: 2,
+            }
+        );
+        assert!((optimization.suboptimal_fraction() - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(game.best_choice(rock), paper);
+    }
+
+    #[test]
+    fn day_two_tournament() {
+        let game = Game::rock_paper_scissors();
+        let rock = game.weapon("rock").unwrap();
+
+        let mut always_rock = Always::new(&game, rock);
+        let mut beater = LastMoveBeater;
+        assert_eq!(always_rock.name(), "always rock");
+        // The beater opens with rock, then plays paper for good.
+        assert_eq!(
+            play_match(&game, &mut always_rock, &mut beater, 10),
+            (4 + 9, 4 + 9 * 8)
+        );
+
+        let mut random = SeededRandom::new(7);
+        let first: Vec<Choice> = (0..20).map(|_| random.choose(&game, &[])).collect();
+        random.reset();
+        let second: Vec<Choice> = (0..20).map(|_| random.choose(&game, &[])).collect();
+        assert_eq!(first, second);
+        assert!(first.iter().all(|choice| choice.0 < 3));
+
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(FixedGuide::new(&game, "A Y\nB X\nC Z\n", &Match::Choice)),
+            Box::new(Always::new(&game, rock)),
+            Box::new(FrequencyCounter),
+            Box::new(LastMoveBeater),
+            Box::new(SeededRandom::new(42)),
+        ];
+        let result = tournament(&game, &mut strategies, 100);
+        assert_eq!(result.names[1], "always rock");
+        assert_eq!(result.head_to_head[1][1], None);
+        assert_eq!(result.head_to_head[2][1], Some(4 + 8 * 99));
+        assert_eq!(result.head_to_head[1][3], Some(4 + 99));
+        for standing in &result.standings {
+            assert_eq!(standing.wins + standing.draws + standing.losses, 4);
+        }
+        let total: u64 = result.head_to_head.iter().flatten().flatten().sum();
+        assert_eq!(total, result.standings.iter().map(|s| s.points).sum());
+        assert!(result
+            .standings
+            .windows(2)
+            .all(|pair| (pair[0].wins, pair[0].points) >= (pair[1].wins, pair[1].points)));
+        assert!(result.to_string().contains("frequency counter"));
     }
 }
@@ -0,0 +1,113 @@
+use crate::{Choice, Error, Game, Outcome};
+use std::collections::BTreeMap;
+
+/// How the symbols of a strategy guide decode into weapons and outcomes.
+///
+/// The first column always names the opponent's weapon. The second one is
+/// read through `player` or `outcome` depending on the [`crate::Match`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolMap {
+    opponent: BTreeMap<String, Choice>,
+    player: BTreeMap<String, Choice>,
+    outcome: BTreeMap<String, Outcome>,
+}
+
+impl SymbolMap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_opponent(mut self, symbol: &str, choice: Choice) -> Self {
+        self.opponent.insert(symbol.to_string(), choice);
+        self
+    }
+
+    #[must_use]
+    pub fn with_player(mut self, symbol: &str, choice: Choice) -> Self {
+        self.player.insert(symbol.to_string(), choice);
+        self
+    }
+
+    #[must_use]
+    pub fn with_outcome(mut self, symbol: &str, outcome: Outcome) -> Self {
+        self.outcome.insert(symbol.to_string(), outcome);
+        self
+    }
+
+    /// Parses a mapping for `game`, one symbol per line:
+    ///
+    /// ```text
+    /// opponent <symbol> <weapon>
+    /// player <symbol> <weapon>
+    /// outcome <symbol> <lose|draw|win>
+    /// ```
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if a line is not a mapping or names an unknown
+    /// weapon or outcome.
+    pub fn parse(spec: &str, game: &Game) -> Result<Self, Error> {
+        let mut symbols = Self::new();
+        for (index, line) in spec.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |what: &str| Error::InvalidSymbols(format!("line {}: {what}", index + 1));
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let [column, symbol, target] = words[..] else {
+                return Err(invalid("expected `<column> <symbol> <target>`"));
+            };
+            let weapon = || {
+                game.weapon(target)
+                    .ok_or_else(|| invalid(&format!("unknown weapon `{target}`")))
+            };
+            symbols = match column {
+                "opponent" => symbols.with_opponent(symbol, weapon()?),
+                "player" => symbols.with_player(symbol, weapon()?),
+                "outcome" => {
+                    let outcome = match target {
+                        "lose" => Outcome::Lose,
+                        "draw" => Outcome::Draw,
+                        "win" => Outcome::Win,
+                        _ => return Err(invalid(&format!("unknown outcome `{target}`"))),
+                    };
+                    symbols.with_outcome(symbol, outcome)
+                }
+                _ => return Err(invalid(&format!("unknown column `{column}`"))),
+            };
+        }
+
+        Ok(symbols)
+    }
+
+    #[must_use]
+    pub fn opponent(&self, symbol: &str) -> Option<Choice> {
+        self.opponent.get(symbol).copied()
+    }
+
+    #[must_use]
+    pub fn player(&self, symbol: &str) -> Option<Choice> {
+        self.player.get(symbol).copied()
+    }
+
+    #[must_use]
+    pub fn outcome(&self, symbol: &str) -> Option<Outcome> {
+        self.outcome.get(symbol).copied()
+    }
+
+    /// The symbols the second column uses for weapons, in order.
+    pub fn player_symbols(&self) -> impl Iterator<Item = &str> {
+        self.player.keys().map(String::as_str)
+    }
+
+    /// The symbols the second column uses for outcomes, in order.
+    pub fn outcome_symbols(&self) -> impl Iterator<Item = &str> {
+        self.outcome.keys().map(String::as_str)
+    }
+}
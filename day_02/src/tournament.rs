@@ -0,0 +1,320 @@
+use crate::{Choice, Game, Match};
+use std::{cmp::Reverse, fmt};
+
+/// A player in a [`tournament`].
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    /// Called before every match.
+    fn reset(&mut self) {}
+
+    /// Picks the next weapon, given the rounds played so far in this match
+    /// as `(own weapon, opponent's weapon)`.
+    fn choose(&mut self, game: &Game, history: &[(Choice, Choice)]) -> Choice;
+}
+
+/// Replays the player's side of a strategy guide, over and over.
+#[derive(Debug, Clone)]
+pub struct FixedGuide {
+    name: String,
+    moves: Vec<Choice>,
+}
+
+impl FixedGuide {
+    #[must_use]
+    pub fn new(game: &Game, guide: &str, match_type: &Match) -> Self {
+        Self {
+            name: "fixed guide".to_string(),
+            moves: game
+                .breakdown(guide, match_type)
+                .iter()
+                .map(|round| round.player)
+                .collect(),
+        }
+    }
+}
+
+impl Strategy for FixedGuide {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose(&mut self, _: &Game, history: &[(Choice, Choice)]) -> Choice {
+        self.moves
+            .get(history.len() % self.moves.len().max(1))
+            .copied()
+            .unwrap_or(Choice(0))
+    }
+}
+
+/// Always plays the same weapon.
+#[derive(Debug, Clone)]
+pub struct Always {
+    name: String,
+    choice: Choice,
+}
+
+impl Always {
+    #[must_use]
+    pub fn new(game: &Game, choice: Choice) -> Self {
+        let weapon = game
+            .weapons()
+            .get(choice.0)
+            .map_or("?", |weapon| weapon.name.as_str());
+        Self {
+            name: format!("always {weapon}"),
+            choice,
+        }
+    }
+}
+
+impl Strategy for Always {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose(&mut self, _: &Game, _: &[(Choice, Choice)]) -> Choice {
+        self.choice
+    }
+}
+
+/// Plays the best response to the opponent's most frequent weapon so far.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> &'static str {
+        "frequency counter"
+    }
+
+    fn choose(&mut self, game: &Game, history: &[(Choice, Choice)]) -> Choice {
+        let mut counts = vec![0usize; game.weapons().len()];
+        for &(_, opponent) in history {
+            counts[opponent.0] += 1;
+        }
+        let favourite = counts
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &count)| Reverse(count))
+            .map_or(Choice(0), |(weapon, _)| Choice(weapon));
+
+        if history.is_empty() {
+            Choice(0)
+        } else {
+            game.best_choice(favourite)
+        }
+    }
+}
+
+/// Plays the best response to the opponent's previous weapon.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LastMoveBeater;
+
+impl Strategy for LastMoveBeater {
+    fn name(&self) -> &'static str {
+        "last move beater"
+    }
+
+    fn choose(&mut self, game: &Game, history: &[(Choice, Choice)]) -> Choice {
+        history
+            .last()
+            .map_or(Choice(0), |&(_, opponent)| game.best_choice(opponent))
+    }
+}
+
+/// Picks weapons uniformly at random from a seeded generator, replaying the
+/// same sequence in every match.
+#[derive(Debug, Clone)]
+pub struct SeededRandom {
+    name: String,
+    seed: u64,
+    state: u64,
+}
+
+impl SeededRandom {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            name: format!("random ({seed})"),
+            seed,
+            state: seed,
+        }
+    }
+
+    /// `SplitMix64`.
+    const fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl Strategy for SeededRandom {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn reset(&mut self) {
+        self.state = self.seed;
+    }
+
+    fn choose(&mut self, game: &Game, _: &[(Choice, Choice)]) -> Choice {
+        let weapons = game.weapons().len().max(1) as u64;
+        Choice(usize::try_from(self.next() % weapons).unwrap_or_default())
+    }
+}
+
+/// Plays `rounds` rounds between `a` and `b` and returns their scores.
+pub fn play_match(
+    game: &Game,
+    a: &mut dyn Strategy,
+    b: &mut dyn Strategy,
+    rounds: usize,
+) -> (u64, u64) {
+    a.reset();
+    b.reset();
+    let mut history_a = Vec::with_capacity(rounds);
+    let mut history_b = Vec::with_capacity(rounds);
+    let (mut score_a, mut score_b) = (0, 0);
+
+    for _ in 0..rounds {
+        let choice_a = a.choose(game, &history_a);
+        let choice_b = b.choose(game, &history_b);
+        score_a += game.score(choice_b, choice_a);
+        score_b += game.score(choice_a, choice_b);
+        history_a.push((choice_a, choice_b));
+        history_b.push((choice_b, choice_a));
+    }
+
+    (score_a, score_b)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    /// Matches won, drawn and lost, by comparing the match scores.
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub points: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TournamentResult {
+    /// Best first: most matches won, then most points.
+    pub standings: Vec<Standing>,
+    pub names: Vec<String>,
+    /// `head_to_head[a][b]` is what strategy `a` scored against `b`, in the
+    /// order the strategies were given.
+    pub head_to_head: Vec<Vec<Option<u64>>>,
+}
+
+/// Lets every strategy play every other one once, over `rounds` rounds.
+pub fn tournament(
+    game: &Game,
+    strategies: &mut [Box<dyn Strategy>],
+    rounds: usize,
+) -> TournamentResult {
+    let count = strategies.len();
+    let mut head_to_head = vec![vec![None; count]; count];
+    let mut standings: Vec<Standing> = strategies
+        .iter()
+        .map(|strategy| Standing {
+            name: strategy.name().to_string(),
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            points: 0,
+        })
+        .collect();
+
+    for a in 0..count {
+        for b in (a + 1)..count {
+            let (left, right) = strategies.split_at_mut(b);
+            let (score_a, score_b) = play_match(game, left[a].as_mut(), right[0].as_mut(), rounds);
+            head_to_head[a][b] = Some(score_a);
+            head_to_head[b][a] = Some(score_b);
+
+            standings[a].points += score_a;
+            standings[b].points += score_b;
+            match score_a.cmp(&score_b) {
+                std::cmp::Ordering::Greater => {
+                    standings[a].wins += 1;
+                    standings[b].losses += 1;
+                }
+                std::cmp::Ordering::Less => {
+                    standings[a].losses += 1;
+                    standings[b].wins += 1;
+                }
+                std::cmp::Ordering::Equal => {
+                    standings[a].draws += 1;
+                    standings[b].draws += 1;
+                }
+            }
+        }
+    }
+
+    let names = standings
+        .iter()
+        .map(|standing| standing.name.clone())
+        .collect();
+    standings.sort_by_key(|standing| Reverse((standing.wins, standing.points)));
+
+    TournamentResult {
+        standings,
+        names,
+        head_to_head,
+    }
+}
+
+impl fmt::Display for TournamentResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels: Vec<String> = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| format!("{} {name}", index + 1))
+            .collect();
+        let width = self
+            .names
+            .iter()
+            .map(String::len)
+            .chain(["Strategy".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "{:<width$} {:>4} {:>4} {:>4} {:>8}",
+            "Strategy", "W", "D", "L", "Points"
+        )?;
+        for standing in &self.standings {
+            writeln!(
+                f,
+                "{:<width$} {:>4} {:>4} {:>4} {:>8}",
+                standing.name, standing.wins, standing.draws, standing.losses, standing.points
+            )?;
+        }
+
+        let width = labels.iter().map(String::len).max().unwrap_or_default();
+        write!(f, "\n{:<width$}", "")?;
+        for index in 0..self.names.len() {
+            write!(f, " {:>8}", index + 1)?;
+        }
+        writeln!(f)?;
+        for (label, row) in labels.iter().zip(&self.head_to_head) {
+            write!(f, "{label:<width$}")?;
+            for score in row {
+                match score {
+                    Some(score) => write!(f, " {score:>8}")?,
+                    None => write!(f, " {:>8}", "-")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{Error, ItemSet};
+use std::collections::HashMap;
+
+/// The items rucksacks may hold and their priorities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    /// Every item with its priority, in definition order: the position of
+    /// an item is its bit in an [`ItemSet`].
+    entries: Vec<(char, u64)>,
+    positions: HashMap<char, usize>,
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::letters()
+    }
+}
+
+impl Alphabet {
+    /// The most items an alphabet can hold.
+    pub const CAPACITY: usize = 64;
+
+    /// The puzzle's alphabet: `a` to `z` are worth 1 to 26 and `A` to `Z`
+    /// 27 to 52.
+    #[must_use]
+    pub fn letters() -> Self {
+        let entries = ('a'..='z').chain('A'..='Z').zip(1..);
+        Self::new(entries).unwrap_or_else(|_| unreachable!("the letters are a valid alphabet"))
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if an item is defined twice or there are more than
+    /// [`Alphabet::CAPACITY`] items.
+    pub fn new(entries: impl IntoIterator<Item = (char, u64)>) -> Result<Self, Error> {
+        let mut alphabet = Self {
+            entries: vec![],
+            positions: HashMap::new(),
+        };
+        for (item, priority) in entries {
+            alphabet.insert(item, priority)?;
+        }
+
+        Ok(alphabet)
+    }
+
+    fn insert(&mut self, item: char, priority: u64) -> Result<(), Error> {
+        if self.positions.contains_key(&item) {
+            return Err(Error::InvalidAlphabet(format!("`{item}` is defined twice")));
+        }
+        if self.entries.len() == Self::CAPACITY {
+            return Err(Error::InvalidAlphabet(format!(
+                "more than {} items",
+                Self::CAPACITY
+            )));
+        }
+        self.positions.insert(item, self.entries.len());
+        self.entries.push((item, priority));
+
+        Ok(())
+    }
+
+    /// Parses an alphabet, one item or range of items per line:
+    ///
+    /// ```text
+    /// a-z 1
+    /// 0-9 53
+    /// ★ 100
+    /// ```
+    ///
+    /// A range gives its first item the priority and each following item
+    /// one more. Blank lines and lines starting with `#` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if a line is not an item with a priority or the
+    /// items do not make a valid alphabet, see [`Alphabet::new`].
+    pub fn parse(spec: &str) -> Result<Self, Error> {
+        let mut alphabet = Self::new([])?;
+        for (index, line) in spec.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid =
+                |what: &str| Error::InvalidAlphabet(format!("line {}: {what}", index + 1));
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let [items, priority] = words[..] else {
+                return Err(invalid("expected `<item or range> <priority>`"));
+            };
+            let priority: u64 = priority
+                .parse()
+                .map_err(|_| invalid(&format!("invalid priority `{priority}`")))?;
+            let items = match items.chars().collect::<Vec<char>>()[..] {
+                [item] => item..=item,
+                [first, '-', last] if first <= last => first..=last,
+                _ => return Err(invalid(&format!("invalid item or range `{items}`"))),
+            };
+            for (offset, item) in (0..).zip(items) {
+                let priority = priority
+                    .checked_add(offset)
+                    .ok_or_else(|| invalid(&format!("priority of `{item}` is too large")))?;
+                alphabet
+                    .insert(item, priority)
+                    .map_err(|error| match error {
+                        Error::InvalidAlphabet(what) => invalid(&what),
+                        error => error,
+                    })?;
+            }
+        }
+
+        Ok(alphabet)
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn priority(&self, item: char) -> Option<u64> {
+        self.positions
+            .get(&item)
+            .map(|&position| self.entries[position].1)
+    }
+
+    /// The set of the known items in `items`, ignoring anything else.
+    #[must_use]
+    pub fn set(&self, items: &str) -> ItemSet {
+        items
+            .chars()
+            .filter_map(|item| self.positions.get(&item))
+            .fold(ItemSet::new(), |set, &position| set.with(position))
+    }
+
+    /// Every item of `set` with its priority, in definition order.
+    pub fn items(&self, set: ItemSet) -> impl Iterator<Item = (char, u64)> + '_ {
+        set.positions().map(|position| self.entries[position])
+    }
+}
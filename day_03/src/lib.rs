@@ -1,15 +1,49 @@
-use array_tool::vec::Intersect;
-use std::{fs, io::Error};
+mod alphabet;
+mod rucksack;
 
-fn char_to_priority(c: char) -> u64 {
-    if c.is_ascii_lowercase() {
-        return u64::from(c) - 96;
-    }
-    u64::from(c) - 38
-}
+pub use alphabet::Alphabet;
+
+pub use rucksack::{
+    analyze_groups, analyze_rucksacks, check_items, discover_groups, parse_rucksacks, Anomaly,
+    GroupReport, ItemSet, Rucksack, RucksackReport,
+};
+
+use common::Answer;
+use std::fs;
 
-const fn is_even(n: usize) -> bool {
-    n % 2 == 0
+#[derive(Debug)]
+pub enum Error {
+    IO(std::io::Error),
+    InvalidGroupSize,
+    /// The last group starts at `line` but only has `found` rucksacks.
+    IncompleteGroup {
+        size: usize,
+        line: usize,
+        found: usize,
+    },
+    NoGrouping,
+    InvalidAlphabet(String),
+    /// `item` at `column` (1-based) of `line` is not in the alphabet.
+    InvalidItem {
+        line: usize,
+        column: usize,
+        item: char,
+    },
+    /// The rucksack on `line` holds an odd number of items.
+    UnevenCompartments {
+        line: usize,
+        len: usize,
+    },
+    /// The compartments of the rucksack on `line`, or the rucksacks of the
+    /// group starting on it, have no item in common.
+    NoSharedItem {
+        line: usize,
+    },
+    /// The compartments of the rucksack on `line`, or the rucksacks of the
+    /// group starting on it, have more than one item in common.
+    SeveralSharedItems {
+        line: usize,
+    },
 }
 
 /// TODO
@@ -18,94 +52,276 @@ const fn is_even(n: usize) -> bool {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_three_part_one(path: &str) -> Result<u64, Error> {
-    let score: u64 = fs::read_to_string(path)?
-        .lines()
-        .filter_map(|items| {
-            if is_even(items.len())
-                && items
-                    .chars()
-                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_uppercase())
-            {
-                let half = items.len() / 2;
-                let first_compartment: Vec<char> = items.chars().take(half).collect();
-                let second_compartment: Vec<char> = items.chars().skip(half).take(half).collect();
-                let intersection = first_compartment.intersect(second_compartment);
-                let mistake = intersection.first();
-                if let Some(mistake) = mistake {
-                    return Some(char_to_priority(*mistake));
-                }
-            }
-            None
-        })
-        .sum();
-
-    Ok(score)
+pub fn day_three_part_one(path: &str) -> Result<Answer, Error> {
+    let input = fs::read_to_string(path).map_err(Error::IO)?;
+
+    day_three_part_one_from_str(&input)
 }
 
-/// TODO
+/// Solves [`day_three_part_one`] from the puzzle text in `input`.
 ///
 /// # Errors
 ///
-/// Will return `Err` if `path` does not exist or the user does not have
-/// permission to read it.
-pub fn day_three_part_two(path: &str) -> Result<u64, Error> {
-    let file = fs::read_to_string(path)?;
-    let group: Vec<&str> = file.lines().collect();
-    let score: u64 = group
-        .chunks_exact(3)
-        .filter_map(|bags| {
-            let bags: Vec<Vec<char>> = bags.iter().map(|s| s.chars().collect()).collect();
-            let team = bags[0]
-                .intersect(bags[1].clone())
-                .intersect(bags[2].clone())
-                .first()
-                .map(|&c| char_to_priority(c));
-            team
-        })
-        .sum();
-
-    Ok(score)
+/// Will return `Err` if a rucksack holds an item that is not a letter or
+/// does not have exactly one misplaced item.
+pub fn day_three_part_one_from_str(input: &str) -> Result<Answer, Error> {
+    rucksack_priorities(input, &Alphabet::default())
+}
+
+/// Sums the priorities of the badges of every group of three elves.
+///
+/// # Errors
+///
+/// Will return `Err` if `path` cannot be read or the last group is
+/// incomplete.
+pub fn day_three_part_two(path: &str) -> Result<Answer, Error> {
+    let input = fs::read_to_string(path).map_err(Error::IO)?;
+
+    day_three_part_two_from_str(&input)
+}
+
+/// Solves [`day_three_part_two`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if a rucksack holds an item that is not a letter, the
+/// last group is incomplete or a group does not have exactly one badge.
+pub fn day_three_part_two_from_str(input: &str) -> Result<Answer, Error> {
+    badge_priorities(input, 3, &Alphabet::default())
+}
+
+/// Sums the priorities of the items misplaced in both compartments of the
+/// rucksacks, as `alphabet` rates them.
+///
+/// # Errors
+///
+/// Will return `Err` if a rucksack holds an item missing from `alphabet`,
+/// or it has an [`Anomaly`].
+pub fn rucksack_priorities(input: &str, alphabet: &Alphabet) -> Result<Answer, Error> {
+    let rucksacks = parse_rucksacks(input, alphabet);
+    check_items(&rucksacks)?;
+    let score = analyze_rucksacks(&rucksacks, alphabet)
+        .iter()
+        .map(RucksackReport::checked_priority)
+        .sum::<Result<u64, Error>>()?;
+
+    Ok(Answer::from(score))
+}
+
+/// Sums the priorities of the badges of every group of `size` elves, as
+/// `alphabet` rates them.
+///
+/// # Errors
+///
+/// Will return `Err` if `size` is 0, the last group is incomplete, a
+/// rucksack holds an item missing from `alphabet` or a group has an
+/// [`Anomaly`].
+pub fn badge_priorities(input: &str, size: usize, alphabet: &Alphabet) -> Result<Answer, Error> {
+    let rucksacks = parse_rucksacks(input, alphabet);
+    check_items(&rucksacks)?;
+    let score = analyze_groups(&rucksacks, size, alphabet)?
+        .iter()
+        .map(GroupReport::checked_priority)
+        .sum::<Result<u64, Error>>()?;
+
+    Ok(Answer::from(score))
 }
 
 #[cfg(test)]
 mod tests {
     use super::day_three_part_one;
-    use crate::{char_to_priority, day_three_part_two};
+    use crate::{
+        analyze_groups, analyze_rucksacks, badge_priorities, day_three_part_one_from_str,
+        day_three_part_two, discover_groups, parse_rucksacks, rucksack_priorities, Alphabet,
+        Anomaly, Error,
+    };
+    use common::Answer;
 
     #[test]
     fn day_three_part_one_example() {
         let result = day_three_part_one("example.txt").unwrap();
-        assert_eq!(result, 157);
+        assert_eq!(result, Answer::from(157));
     }
 
     #[test]
-    fn char_to_priority_works() {
-        let result = char_to_priority('a');
-        assert_eq!(result, 1);
-        let result = char_to_priority('z');
-        assert_eq!(result, 26);
-        let result = char_to_priority('A');
-        assert_eq!(result, 27);
-        let result = char_to_priority('Z');
-        assert_eq!(result, 52);
+    fn default_alphabet_priorities() {
+        let alphabet = Alphabet::default();
+        assert_eq!(alphabet.priority('a'), Some(1));
+        assert_eq!(alphabet.priority('z'), Some(26));
+        assert_eq!(alphabet.priority('A'), Some(27));
+        assert_eq!(alphabet.priority('Z'), Some(52));
+        assert_eq!(alphabet.priority('!'), None);
+
+        assert!(matches!(
+            day_three_part_one_from_str("abcA\nab!a\n"),
+            Err(Error::InvalidItem {
+                line: 2,
+                column: 3,
+                item: '!'
+            })
+        ));
     }
 
     #[test]
     fn day_three_part_one_data() {
         let result = day_three_part_one("data.txt").unwrap();
-        assert_eq!(result, 8123);
+        assert_eq!(result, Answer::from(8123));
     }
 
     #[test]
     fn day_three_part_two_example() {
         let result = day_three_part_two("example.txt").unwrap();
-        assert_eq!(result, 70);
+        assert_eq!(result, Answer::from(70));
     }
 
     #[test]
     fn day_three_part_two_data() {
         let result = day_three_part_two("data.txt").unwrap();
-        assert_eq!(result, 2620);
+        assert_eq!(result, Answer::from(2620));
+    }
+
+    #[test]
+    fn rucksack_anomalies() {
+        let letters = Alphabet::letters();
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\nabcabc\nabcd\n\nabcab\nab1b\n";
+        let rucksacks = parse_rucksacks(input, &letters);
+        assert_eq!(rucksacks.len(), 5);
+        assert_eq!(rucksacks[4].line, 6);
+
+        let reports = analyze_rucksacks(&rucksacks, &letters);
+        assert_eq!(reports[0].shared, [('p', 16)]);
+        assert_eq!(reports[0].priority(), Some(16));
+        assert_eq!(reports[1].anomaly, Some(Anomaly::SeveralSharedItems));
+        assert_eq!(reports[1].shared, [('a', 1), ('b', 2), ('c', 3)]);
+        assert_eq!(reports[2].anomaly, Some(Anomaly::NoSharedItem));
+        assert_eq!(reports[2].priority(), None);
+        assert_eq!(
+            reports[3].anomaly,
+            Some(Anomaly::UnevenCompartments { len: 5 })
+        );
+        assert_eq!(
+            reports[4].anomaly,
+            Some(Anomaly::InvalidItem {
+                column: 3,
+                item: '1'
+            })
+        );
+
+        let valid = "vJrwpWtwJgWrhcsFMMfFFhFp\n";
+        assert_eq!(
+            rucksack_priorities(valid, &letters).unwrap(),
+            Answer::from(16)
+        );
+        assert!(matches!(
+            rucksack_priorities(&format!("{valid}abcabc\n"), &letters),
+            Err(Error::SeveralSharedItems { line: 2 })
+        ));
+        assert!(matches!(
+            rucksack_priorities(&format!("{valid}{valid}abcd\n"), &letters),
+            Err(Error::NoSharedItem { line: 3 })
+        ));
+        assert!(matches!(
+            rucksack_priorities(&format!("{valid}\nabcab\n"), &letters),
+            Err(Error::UnevenCompartments { line: 3, len: 5 })
+        ));
+        assert!(matches!(
+            badge_priorities("ab\nac\ncd\ncd\n", 2, &letters),
+            Err(Error::SeveralSharedItems { line: 3 })
+        ));
+
+        let example = std::fs::read_to_string("example.txt").unwrap();
+        let groups = analyze_groups(&parse_rucksacks(&example, &letters), 3, &letters).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].lines, [1, 2, 3]);
+        assert_eq!(groups[0].badges, [('r', 18)]);
+        assert_eq!(groups[1].priority(), Some(52));
+
+        let groups = analyze_groups(&rucksacks[1..4], 3, &letters).unwrap();
+        assert_eq!(groups[0].badges.len(), 3);
+        assert_eq!(groups[0].anomaly, Some(Anomaly::SeveralSharedItems));
+    }
+
+    #[test]
+    fn rucksack_groupings() {
+        let letters = Alphabet::letters();
+        let input = std::fs::read_to_string("example.txt").unwrap();
+        assert_eq!(
+            badge_priorities(&input, 3, &letters).unwrap(),
+            Answer::from(70)
+        );
+        assert!(matches!(
+            badge_priorities(&input, 4, &letters),
+            Err(Error::IncompleteGroup {
+                size: 4,
+                line: 5,
+                found: 2
+            })
+        ));
+        assert!(matches!(
+            badge_priorities(&input, 0, &letters),
+            Err(Error::InvalidGroupSize)
+        ));
+
+        let rucksacks = parse_rucksacks(&input, &letters);
+        let groups = discover_groups(&rucksacks, &letters).unwrap();
+        let lines: Vec<&[usize]> = groups.iter().map(|group| &group.lines[..]).collect();
+        assert_eq!(lines, [&[1, 2, 3][..], &[4, 5, 6]]);
+        assert!(matches!(
+            discover_groups(&rucksacks[..5], &letters),
+            Err(Error::NoGrouping)
+        ));
+
+        let rucksacks = parse_rucksacks("ab\nac\nbd\nbe\nbf\n", &letters);
+        let groups = discover_groups(&rucksacks, &letters).unwrap();
+        let badges: Vec<&[(char, u64)]> = groups.iter().map(|group| &group.badges[..]).collect();
+        assert_eq!(badges, [&[('a', 1)][..], &[('b', 2)]]);
+        assert_eq!(groups[1].lines, [3, 4, 5]);
+    }
+
+    #[test]
+    fn item_alphabets() {
+        let alphabet = Alphabet::parse("# digits then runes\n0-9 1\n\nᚠ 100\n").unwrap();
+        assert_eq!(alphabet.len(), 11);
+        assert_eq!(alphabet.priority('0'), Some(1));
+        assert_eq!(alphabet.priority('9'), Some(10));
+        assert_eq!(alphabet.priority('ᚠ'), Some(100));
+        assert_eq!(alphabet.priority('a'), None);
+        assert_eq!(
+            rucksack_priorities("12ᚠ3ᚠ4\n5945\n", &alphabet).unwrap(),
+            Answer::from(100 + 6)
+        );
+        assert!(matches!(
+            rucksack_priorities("1212\n12a2\n", &alphabet),
+            Err(Error::InvalidItem {
+                line: 2,
+                column: 3,
+                item: 'a'
+            })
+        ));
+
+        let letters = Alphabet::default();
+        assert_eq!(letters, Alphabet::parse("a-z 1\nA-Z 27").unwrap());
+        assert_eq!(
+            rucksack_priorities(&std::fs::read_to_string("data.txt").unwrap(), &letters).unwrap(),
+            Answer::from(8123)
+        );
+
+        assert!(matches!(
+            Alphabet::parse("a 1\nb 2\na 3"),
+            Err(Error::InvalidAlphabet(message)) if message == "line 3: `a` is defined twice"
+        ));
+        assert!(matches!(
+            Alphabet::parse("z-a 1"),
+            Err(Error::InvalidAlphabet(_))
+        ));
+        assert!(matches!(
+            Alphabet::parse("a-z 1\nA-Z 27\n0-9 53\n+ 63\n- 64\n* 65"),
+            Err(Error::InvalidAlphabet(message)) if message == "line 6: more than 64 items"
+        ));
+        assert!(matches!(
+            Alphabet::parse("a 1\nx-z 18446744073709551614"),
+            Err(Error::InvalidAlphabet(message)) if message == "line 2: priority of `z` is too large"
+        ));
+        assert!(Alphabet::new([('x', 1), ('x', 2)]).is_err());
     }
 }
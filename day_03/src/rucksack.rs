@@ -0,0 +1,328 @@
+use crate::{Alphabet, Error};
+
+/// A set of items of an [`Alphabet`], one bit per item.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Adds the item at `position` in its alphabet.
+    #[must_use]
+    pub const fn with(self, position: usize) -> Self {
+        Self(self.0 | 1 << position)
+    }
+
+    #[must_use]
+    pub const fn contains(self, position: usize) -> bool {
+        position < Alphabet::CAPACITY && self.0 & 1 << position != 0
+    }
+
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    #[must_use]
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The alphabet positions of the items, in order.
+    pub fn positions(self) -> impl Iterator<Item = usize> {
+        (0..Alphabet::CAPACITY).filter(move |&position| self.contains(position))
+    }
+}
+
+/// Something unexpected about a rucksack or a group of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anomaly {
+    /// The first character at `column` (1-based) that is not an item.
+    InvalidItem {
+        column: usize,
+        item: char,
+    },
+    /// An odd number of items, which cannot be split evenly.
+    UnevenCompartments {
+        len: usize,
+    },
+    NoSharedItem,
+    SeveralSharedItems,
+}
+
+impl Anomaly {
+    /// The anomaly as an error about the rucksack on `line`, or the group
+    /// starting on it.
+    #[must_use]
+    pub const fn error(self, line: usize) -> Error {
+        match self {
+            Self::InvalidItem { column, item } => Error::InvalidItem { line, column, item },
+            Self::UnevenCompartments { len } => Error::UnevenCompartments { line, len },
+            Self::NoSharedItem => Error::NoSharedItem { line },
+            Self::SeveralSharedItems => Error::SeveralSharedItems { line },
+        }
+    }
+}
+
+/// A rucksack as two compartments of items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    /// 1-based line of the rucksack in the input.
+    pub line: usize,
+    pub first: ItemSet,
+    pub second: ItemSet,
+    /// Why the rucksack cannot be trusted, if at all.
+    pub defect: Option<Anomaly>,
+}
+
+impl Rucksack {
+    /// Splits `items` into two compartments, the second one taking the
+    /// middle item of an odd number of them. Characters missing from
+    /// `alphabet` are left out.
+    #[must_use]
+    pub fn parse(line: usize, items: &str, alphabet: &Alphabet) -> Self {
+        let len = items.chars().count();
+        let invalid = items
+            .chars()
+            .enumerate()
+            .find(|&(_, item)| alphabet.priority(item).is_none())
+            .map(|(index, item)| Anomaly::InvalidItem {
+                column: index + 1,
+                item,
+            });
+        let uneven = (len % 2 == 1).then_some(Anomaly::UnevenCompartments { len });
+        let half = items
+            .char_indices()
+            .nth(len / 2)
+            .map_or(items.len(), |(index, _)| index);
+
+        Self {
+            line,
+            first: alphabet.set(&items[..half]),
+            second: alphabet.set(&items[half..]),
+            defect: invalid.or(uneven),
+        }
+    }
+
+    #[must_use]
+    pub const fn items(&self) -> ItemSet {
+        self.first.union(self.second)
+    }
+
+    /// The items found in both compartments.
+    #[must_use]
+    pub const fn shared(&self) -> ItemSet {
+        self.first.intersection(self.second)
+    }
+}
+
+/// Every non-blank line of `input` as a rucksack holding items of
+/// `alphabet`.
+#[must_use]
+pub fn parse_rucksacks(input: &str, alphabet: &Alphabet) -> Vec<Rucksack> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| Rucksack::parse(index + 1, line, alphabet))
+        .collect()
+}
+
+/// The anomaly of a set of items expected to hold exactly one item.
+const fn single_item(items: ItemSet) -> Option<Anomaly> {
+    match items.len() {
+        0 => Some(Anomaly::NoSharedItem),
+        1 => None,
+        _ => Some(Anomaly::SeveralSharedItems),
+    }
+}
+
+/// The first item of a rucksack that is not in the alphabet, as an error.
+///
+/// # Errors
+///
+/// Will return `Err` if a rucksack holds an item missing from its alphabet.
+pub fn check_items(rucksacks: &[Rucksack]) -> Result<(), Error> {
+    rucksacks
+        .iter()
+        .find_map(|rucksack| match rucksack.defect {
+            Some(anomaly @ Anomaly::InvalidItem { .. }) => Some(anomaly.error(rucksack.line)),
+            _ => None,
+        })
+        .map_or(Ok(()), Err)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RucksackReport {
+    pub line: usize,
+    /// The items found in both compartments, with their priorities.
+    pub shared: Vec<(char, u64)>,
+    pub anomaly: Option<Anomaly>,
+}
+
+impl RucksackReport {
+    /// The priority of the misplaced item, unless there is an anomaly.
+    #[must_use]
+    pub fn priority(&self) -> Option<u64> {
+        self.anomaly
+            .is_none()
+            .then(|| self.shared.iter().map(|(_, priority)| priority).sum())
+    }
+
+    /// The priority of the misplaced item.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an anomaly.
+    pub fn checked_priority(&self) -> Result<u64, Error> {
+        if let Some(anomaly) = self.anomaly {
+            return Err(anomaly.error(self.line));
+        }
+
+        Ok(self.shared.iter().map(|(_, priority)| priority).sum())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupReport {
+    /// The lines of the rucksacks in the group.
+    pub lines: Vec<usize>,
+    /// The items carried by every rucksack of the group, with their
+    /// priorities.
+    pub badges: Vec<(char, u64)>,
+    pub anomaly: Option<Anomaly>,
+}
+
+impl GroupReport {
+    /// The priority of the group's badge, unless there is an anomaly.
+    #[must_use]
+    pub fn priority(&self) -> Option<u64> {
+        self.anomaly
+            .is_none()
+            .then(|| self.badges.iter().map(|(_, priority)| priority).sum())
+    }
+
+    /// The priority of the group's badge.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an anomaly.
+    pub fn checked_priority(&self) -> Result<u64, Error> {
+        if let Some(anomaly) = self.anomaly {
+            return Err(anomaly.error(self.lines.first().copied().unwrap_or_default()));
+        }
+
+        Ok(self.badges.iter().map(|(_, priority)| priority).sum())
+    }
+}
+
+/// Finds the item misplaced in both compartments of every rucksack.
+#[must_use]
+pub fn analyze_rucksacks(rucksacks: &[Rucksack], alphabet: &Alphabet) -> Vec<RucksackReport> {
+    rucksacks
+        .iter()
+        .map(|rucksack| RucksackReport {
+            line: rucksack.line,
+            shared: alphabet.items(rucksack.shared()).collect(),
+            anomaly: rucksack.defect.or_else(|| single_item(rucksack.shared())),
+        })
+        .collect()
+}
+
+fn group_report(group: &[Rucksack], alphabet: &Alphabet) -> GroupReport {
+    let badges = group
+        .iter()
+        .map(Rucksack::items)
+        .reduce(ItemSet::intersection)
+        .unwrap_or_default();
+
+    GroupReport {
+        lines: group.iter().map(|rucksack| rucksack.line).collect(),
+        badges: alphabet.items(badges).collect(),
+        anomaly: single_item(badges),
+    }
+}
+
+/// Finds the badge of every group of `size` consecutive rucksacks.
+///
+/// # Errors
+///
+/// Will return `Err` if `size` is 0 or the last group has fewer than
+/// `size` rucksacks.
+pub fn analyze_groups(
+    rucksacks: &[Rucksack],
+    size: usize,
+    alphabet: &Alphabet,
+) -> Result<Vec<GroupReport>, Error> {
+    if size == 0 {
+        return Err(Error::InvalidGroupSize);
+    }
+    let groups = rucksacks.chunks_exact(size);
+    if let [first, ..] = groups.remainder() {
+        return Err(Error::IncompleteGroup {
+            size,
+            line: first.line,
+            found: groups.remainder().len(),
+        });
+    }
+
+    Ok(groups.map(|group| group_report(group, alphabet)).collect())
+}
+
+/// Splits the rucksacks into groups of at least two consecutive rucksacks
+/// sharing exactly one badge, using as few groups as possible. The earliest
+/// group boundaries win ties.
+///
+/// # Errors
+///
+/// Will return `Err` if there is no such split.
+pub fn discover_groups(
+    rucksacks: &[Rucksack],
+    alphabet: &Alphabet,
+) -> Result<Vec<GroupReport>, Error> {
+    // `best[end]` is the fewest groups covering the first `end` rucksacks,
+    // with where its last group starts.
+    let mut best: Vec<Option<(usize, usize)>> = vec![None; rucksacks.len() + 1];
+    best[0] = Some((0, 0));
+
+    for start in 0..rucksacks.len() {
+        let Some((groups, _)) = best[start] else {
+            continue;
+        };
+        let mut badges = rucksacks[start].items();
+        for end in (start + 1)..rucksacks.len() {
+            badges = badges.intersection(rucksacks[end].items());
+            match badges.len() {
+                0 => break,
+                1 if best[end + 1].is_none_or(|(known, _)| groups + 1 < known) => {
+                    best[end + 1] = Some((groups + 1, start));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut groups = vec![];
+    let mut end = rucksacks.len();
+    while end > 0 {
+        let (_, start) = best[end].ok_or(Error::NoGrouping)?;
+        groups.push(group_report(&rucksacks[start..end], alphabet));
+        end = start;
+    }
+    groups.reverse();
+
+    Ok(groups)
+}
@@ -0,0 +1,138 @@
+use crate::{is_redundant, Interval, SectionAssignment};
+
+/// How the assignments of a whole camp cover its sections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    /// Every assigned section, as disjoint intervals in order.
+    pub assigned: Vec<Interval>,
+    /// The sections of the camp nobody is assigned to.
+    pub unassigned: Vec<Interval>,
+    /// The sections assigned to more than one elf.
+    pub multiply_assigned: Vec<Interval>,
+    /// The most elves assigned to any single section.
+    pub max_coverage: usize,
+    /// Where `max_coverage` elves are assigned.
+    pub busiest: Vec<Interval>,
+    /// The groups, by index, where an elf's sections are all covered by
+    /// another elf of the group.
+    pub redundant_groups: Vec<usize>,
+}
+
+impl Coverage {
+    /// Sweeps over the starts and ends of every range in `assignments`,
+    /// so the time depends on the number of ranges and not on how many
+    /// sections they span. Unassigned sections are looked for in `camp`.
+    #[must_use]
+    pub fn new(assignments: &[SectionAssignment], camp: Interval) -> Self {
+        // Where coverage changes and by how much, ends being exclusive so a
+        // range ending at `u32::MAX` still fits.
+        let mut events: Vec<(u64, isize)> = assignments
+            .iter()
+            .flatten()
+            .flat_map(|range| {
+                [
+                    (u64::from(range.start()), 1),
+                    (u64::from(range.end()) + 1, -1),
+                ]
+            })
+            .collect();
+        events.sort_unstable();
+
+        // Sections between consecutive event positions, with their coverage.
+        let mut segments: Vec<(Interval, usize)> = vec![];
+        let mut depth = 0isize;
+        for (index, &(position, change)) in events.iter().enumerate() {
+            depth += change;
+            let Some(&(next, _)) = events.get(index + 1) else {
+                break;
+            };
+            if next > position {
+                let coverage = usize::try_from(depth).unwrap_or_default();
+                if let Some(segment) = section_interval(position, next - 1) {
+                    segments.push((segment, coverage));
+                }
+            }
+        }
+
+        let max_coverage = segments
+            .iter()
+            .map(|&(_, coverage)| coverage)
+            .max()
+            .unwrap_or_default();
+        let covered_by = |least: usize| {
+            merge(
+                segments
+                    .iter()
+                    .filter(|&&(_, coverage)| coverage >= least)
+                    .map(|&(segment, _)| segment),
+            )
+        };
+        let assigned = covered_by(1);
+        let busiest = merge(
+            segments
+                .iter()
+                .filter(|&&(_, coverage)| coverage == max_coverage && max_coverage > 0)
+                .map(|&(segment, _)| segment),
+        );
+
+        Self {
+            unassigned: gaps(camp, &assigned),
+            multiply_assigned: covered_by(2),
+            assigned,
+            max_coverage,
+            busiest,
+            redundant_groups: assignments
+                .iter()
+                .enumerate()
+                .filter(|(_, group)| is_redundant(group))
+                .map(|(index, _)| index)
+                .collect(),
+        }
+    }
+
+    /// The number of sections assigned to at least one elf.
+    #[must_use]
+    pub fn assigned_sections(&self) -> u64 {
+        self.assigned
+            .iter()
+            .map(|range| range.section_count())
+            .sum()
+    }
+}
+
+fn section_interval(start: u64, end: u64) -> Option<Interval> {
+    Interval::new(u32::try_from(start).ok()?, u32::try_from(end).ok()?)
+}
+
+/// Joins sorted intervals that overlap or touch.
+fn merge(intervals: impl Iterator<Item = Interval>) -> Vec<Interval> {
+    let mut merged: Vec<Interval> = vec![];
+    for interval in intervals {
+        if let Some(last) = merged.last_mut() {
+            if let Some(union) = last.union(interval) {
+                *last = union;
+                continue;
+            }
+        }
+        merged.push(interval);
+    }
+
+    merged
+}
+
+/// The sections of `camp` outside the sorted, disjoint `covered`.
+fn gaps(camp: Interval, covered: &[Interval]) -> Vec<Interval> {
+    let mut gaps = vec![];
+    let mut rest = Some(camp);
+    for &range in covered {
+        let Some(remaining) = rest else {
+            break;
+        };
+        let (before, after) = remaining.difference(range);
+        gaps.extend(before);
+        rest = after;
+    }
+    gaps.extend(rest);
+
+    gaps
+}
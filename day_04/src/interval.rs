@@ -0,0 +1,108 @@
+use std::ops::RangeInclusive;
+
+/// A non-empty inclusive range of sections, `start..=end`.
+///
+/// Every relation only looks at the bounds, so it takes the same time
+/// however many sections the intervals span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    start: u32,
+    end: u32,
+}
+
+impl Interval {
+    /// `None` if `start` comes after `end`.
+    #[must_use]
+    pub const fn new(start: u32, end: u32) -> Option<Self> {
+        if start <= end {
+            Some(Self { start, end })
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub const fn start(self) -> u32 {
+        self.start
+    }
+
+    #[must_use]
+    pub const fn end(self) -> u32 {
+        self.end
+    }
+
+    /// The number of sections, which does not fit a `u32` for `0..=u32::MAX`.
+    #[must_use]
+    pub const fn section_count(self) -> u64 {
+        self.end as u64 - self.start as u64 + 1
+    }
+
+    #[must_use]
+    pub const fn contains(self, section: u32) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    /// Whether every section of `other` is also in `self`.
+    #[must_use]
+    pub const fn covers(self, other: Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether `self` and `other` share at least one section.
+    #[must_use]
+    pub const fn overlaps(self, other: Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether one of the intervals starts right after the other ends.
+    #[must_use]
+    pub const fn is_adjacent(self, other: Self) -> bool {
+        (self.end < other.start && self.end + 1 == other.start)
+            || (other.end < self.start && other.end + 1 == self.start)
+    }
+
+    /// The sections in both intervals.
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The sections in either interval, `None` when that is not a single
+    /// interval because there is a gap between them.
+    #[must_use]
+    pub fn union(self, other: Self) -> Option<Self> {
+        (self.overlaps(other) || self.is_adjacent(other)).then(|| Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// The sections of `self` that are not in `other`: what is left before
+    /// `other` and what is left after it.
+    #[must_use]
+    pub fn difference(self, other: Self) -> (Option<Self>, Option<Self>) {
+        if !self.overlaps(other) {
+            return if self.end < other.start {
+                (Some(self), None)
+            } else {
+                (None, Some(self))
+            };
+        }
+        let before = other
+            .start
+            .checked_sub(1)
+            .and_then(|end| Self::new(self.start, end));
+        let after = other
+            .end
+            .checked_add(1)
+            .and_then(|start| Self::new(start, self.end));
+
+        (before, after)
+    }
+}
+
+impl From<Interval> for RangeInclusive<u32> {
+    fn from(interval: Interval) -> Self {
+        interval.start..=interval.end
+    }
+}
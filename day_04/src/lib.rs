@@ -1,55 +1,125 @@
-use std::{fs::read_to_string, ops::RangeInclusive};
+mod coverage;
+mod interval;
+mod tree;
+
+pub use coverage::Coverage;
+pub use interval::Interval;
+pub use tree::{AssignmentTree, ElfId};
+
+use common::Answer;
+use std::fs::read_to_string;
 
 use nom::{
     bytes::complete::tag,
     character::complete::{self, newline},
+    combinator::{eof, map_opt, opt},
     multi::separated_list1,
-    sequence::separated_pair,
-    IResult as NomResult,
+    sequence::{pair, separated_pair, terminated},
+    Err as NomErr, IResult as NomResult,
 };
 
 #[derive(Debug)]
 pub enum Error {
     IO(std::io::Error),
     Nom(String),
+    /// Line `line` (1-based), `text`, is not a group of `start-end`
+    /// ranges with `start <= end`.
+    InvalidLine {
+        line: usize,
+        text: String,
+    },
+}
+
+/// The sections assigned to each elf of a group, usually a pair.
+pub type SectionAssignment = Vec<Interval>;
+
+/// Whether an elf of the group only has sections another elf also has.
+#[must_use]
+pub fn is_redundant(group: &[Interval]) -> bool {
+    group.iter().enumerate().any(|(a, range_a)| {
+        group
+            .iter()
+            .enumerate()
+            .any(|(b, range_b)| a != b && range_a.covers(*range_b))
+    })
 }
 
-type SectionAssignment = (RangeInclusive<u32>, RangeInclusive<u32>);
+/// Whether two elves of the group share a section.
+#[must_use]
+pub fn has_overlap(group: &[Interval]) -> bool {
+    let mut sorted = group.to_vec();
+    sorted.sort_unstable();
 
-fn sections(input: &str) -> NomResult<&str, RangeInclusive<u32>> {
-    let (input, (start, end)) = separated_pair(complete::u32, tag("-"), complete::u32)(input)?;
+    sorted.windows(2).any(|pair| pair[0].overlaps(pair[1]))
+}
 
-    Ok((input, start..=end))
+fn sections(input: &str) -> NomResult<&str, Interval> {
+    map_opt(
+        separated_pair(complete::u32, tag("-"), complete::u32),
+        |(start, end)| Interval::new(start, end),
+    )(input)
 }
 
+/// Every line of `input`, each a group of comma separated ranges, up to
+/// the end but for an optional newline.
 fn section_assignments(input: &str) -> NomResult<&str, Vec<SectionAssignment>> {
+    let group = separated_list1(tag(","), sections);
     let (input, ranges) =
-        separated_list1(newline, separated_pair(sections, tag(","), sections))(input)?;
+        terminated(separated_list1(newline, group), pair(opt(newline), eof))(input)?;
 
     Ok((input, ranges))
 }
 
+/// Parses every group of assignments in `input`, one per line, with an
+/// optional newline at the end.
+///
+/// # Errors
+///
+/// Will return `Err` if a line of `input` is not a valid group of
+/// assignments.
+pub fn parse_assignments(input: &str) -> Result<Vec<SectionAssignment>, Error> {
+    // The line holding the part of `input` that `rest` starts at.
+    let invalid_line = |rest: &str| {
+        let line = input[..input.len() - rest.len()].matches('\n').count();
+        Error::InvalidLine {
+            line: line + 1,
+            text: input.lines().nth(line).unwrap_or_default().to_string(),
+        }
+    };
+    let (_, assignments) = section_assignments(input).map_err(|e| match e {
+        NomErr::Error(e) | NomErr::Failure(e) => invalid_line(e.input),
+        NomErr::Incomplete(_) => Error::Nom(e.to_string()),
+    })?;
+
+    Ok(assignments)
+}
+
 /// TODO
 ///
 /// # Errors
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_four_part_one(path: &str) -> Result<usize, Error> {
+pub fn day_four_part_one(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, section_assignments) =
-        section_assignments(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_four_part_one_from_str(&input)
+}
+
+/// Solves [`day_four_part_one`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_four_part_one_from_str(input: &str) -> Result<Answer, Error> {
+    let section_assignments = parse_assignments(input)?;
 
     let overlapped_ranges_count = section_assignments
         .iter()
-        .filter(|(range_a, range_b)| {
-            let a = range_a.clone().all(|aa| range_b.contains(&aa));
-            let b = range_b.clone().all(|aa| range_a.contains(&aa));
-            a || b
-        })
+        .filter(|group| is_redundant(group))
         .count();
 
-    Ok(overlapped_ranges_count)
+    Ok(Answer::from(overlapped_ranges_count))
 }
 
 /// TODO
@@ -58,49 +128,215 @@ pub fn day_four_part_one(path: &str) -> Result<usize, Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_four_part_two(path: &str) -> Result<usize, Error> {
+pub fn day_four_part_two(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, section_assignments) =
-        section_assignments(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_four_part_two_from_str(&input)
+}
+
+/// Solves [`day_four_part_two`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_four_part_two_from_str(input: &str) -> Result<Answer, Error> {
+    let section_assignments = parse_assignments(input)?;
 
     let overlapped_ranges_count = section_assignments
         .iter()
-        .filter(|(range_a, range_b)| {
-            let a = range_a.clone().any(|aa| range_b.contains(&aa));
-            let b = range_b.clone().any(|aa| range_a.contains(&aa));
-            a || b
-        })
+        .filter(|group| has_overlap(group))
         .count();
 
-    Ok(overlapped_ranges_count)
+    Ok(Answer::from(overlapped_ranges_count))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{day_four_part_one, day_four_part_two};
+    use crate::{
+        day_four_part_one, day_four_part_one_from_str, day_four_part_two,
+        day_four_part_two_from_str, has_overlap, is_redundant, parse_assignments, AssignmentTree,
+        Coverage, ElfId, Error, Interval,
+    };
+    use common::Answer;
 
     #[test]
     fn day_four_part_one_example() {
         let result = day_four_part_one("example.txt").unwrap();
-        assert_eq!(result, 2);
+        assert_eq!(result, Answer::from(2));
     }
 
     #[test]
     fn day_four_part_one_data() {
         let result = day_four_part_one("data.txt").unwrap();
-        assert!(result < 396);
-        assert_eq!(result, 305);
+        assert!(result < Answer::from(396));
+        assert_eq!(result, Answer::from(305));
     }
 
     #[test]
     fn day_four_part_two_example() {
         let result = day_four_part_two("example.txt").unwrap();
-        assert_eq!(result, 4);
+        assert_eq!(result, Answer::from(4));
     }
 
     #[test]
     fn day_four_part_two_data() {
         let result = day_four_part_two("data.txt").unwrap();
-        assert_eq!(result, 811);
+        assert_eq!(result, Answer::from(811));
+    }
+
+    #[test]
+    fn interval_relations() {
+        let interval = |start, end| Interval::new(start, end).unwrap();
+        let a = interval(2, 8);
+        assert_eq!(Interval::new(3, 2), None);
+        assert_eq!(a.section_count(), 7);
+        assert!(a.contains(8) && !a.contains(9));
+        assert!(a.covers(interval(3, 7)) && !interval(3, 7).covers(a));
+        assert!(a.overlaps(interval(8, 9)) && !a.overlaps(interval(9, 9)));
+        assert!(a.is_adjacent(interval(9, 12)) && interval(0, 1).is_adjacent(a));
+        assert!(!a.is_adjacent(interval(8, 12)) && !a.is_adjacent(interval(10, 12)));
+
+        assert_eq!(a.intersection(interval(6, 20)), Some(interval(6, 8)));
+        assert_eq!(a.intersection(interval(9, 20)), None);
+        assert_eq!(a.union(interval(9, 20)), Some(interval(2, 20)));
+        assert_eq!(a.union(interval(10, 20)), None);
+        assert_eq!(
+            a.difference(interval(4, 5)),
+            (Some(interval(2, 3)), Some(interval(6, 8)))
+        );
+        assert_eq!(a.difference(interval(0, 5)), (None, Some(interval(6, 8))));
+        assert_eq!(a.difference(interval(0, 10)), (None, None));
+        assert_eq!(a.difference(interval(9, 10)), (Some(a), None));
+
+        let huge = interval(0, u32::MAX);
+        assert_eq!(huge.section_count(), 1 << 32);
+        assert_eq!(
+            huge.difference(interval(1, u32::MAX)),
+            (Some(interval(0, 0)), None)
+        );
+        assert_eq!(
+            day_four_part_one_from_str("1-4000000000,2-3999999999\n5-6,1-4000000000").unwrap(),
+            Answer::from(2)
+        );
+        assert!(day_four_part_one_from_str("3-2,1-4").is_err());
+        assert!(matches!(
+            day_four_part_one_from_str("2-8,3-7\n5-3,1-9\n1-9,2-3"),
+            Err(Error::InvalidLine { line: 2, text }) if text == "5-3,1-9"
+        ));
+        assert!(matches!(
+            day_four_part_two_from_str("2-8,3-7\n1-9,2-3\nthree to seven\n"),
+            Err(Error::InvalidLine { line: 3, .. })
+        ));
+        assert!(matches!(
+            parse_assignments("2-8,3-7\n\n"),
+            Err(Error::InvalidLine { line: 2, .. })
+        ));
+        assert_eq!(parse_assignments("2-8,3-7\n").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn camp_coverage() {
+        let interval = |start, end| Interval::new(start, end).unwrap();
+        let input = std::fs::read_to_string("example.txt").unwrap();
+        let assignments = parse_assignments(&input).unwrap();
+        let coverage = Coverage::new(&assignments, interval(1, 10));
+
+        assert_eq!(coverage.assigned, [interval(2, 9)]);
+        assert_eq!(coverage.assigned_sections(), 8);
+        assert_eq!(coverage.unassigned, [interval(1, 1), interval(10, 10)]);
+        assert_eq!(coverage.multiply_assigned, [interval(2, 8)]);
+        assert_eq!(coverage.max_coverage, 8);
+        assert_eq!(coverage.busiest, [interval(6, 6)]);
+        assert_eq!(coverage.redundant_groups, [3, 4]);
+
+        let assignments =
+            parse_assignments("1-2,6-4000000000\n4294967290-4294967295,10-20").unwrap();
+        let coverage = Coverage::new(&assignments, interval(0, u32::MAX));
+        assert_eq!(
+            coverage.assigned,
+            [
+                interval(1, 2),
+                interval(6, 4_000_000_000),
+                interval(4_294_967_290, u32::MAX)
+            ]
+        );
+        assert_eq!(
+            coverage.unassigned,
+            [
+                interval(0, 0),
+                interval(3, 5),
+                interval(4_000_000_001, 4_294_967_289)
+            ]
+        );
+        assert_eq!(coverage.max_coverage, 2);
+        assert_eq!(coverage.busiest, [interval(10, 20)]);
+        assert_eq!(coverage.redundant_groups, []);
+
+        let coverage = Coverage::new(&[], interval(1, 3));
+        assert_eq!(coverage.unassigned, [interval(1, 3)]);
+        assert_eq!(coverage.max_coverage, 0);
+        assert!(coverage.busiest.is_empty());
+    }
+
+    #[test]
+    fn assignment_groups_and_tree() {
+        let interval = |start, end| Interval::new(start, end).unwrap();
+        let input = "1-3,5-7,9-9\n2-9,3-4,8-8,1-1\n4-6";
+        let assignments = parse_assignments(input).unwrap();
+        assert_eq!(assignments.len(), 3);
+        assert_eq!(assignments[1].len(), 4);
+        assert!(!is_redundant(&assignments[0]) && !has_overlap(&assignments[0]));
+        assert!(is_redundant(&assignments[1]) && has_overlap(&assignments[1]));
+        assert!(!is_redundant(&assignments[2]) && !has_overlap(&assignments[2]));
+        assert_eq!(day_four_part_one_from_str(input).unwrap(), Answer::from(1));
+        assert_eq!(day_four_part_two_from_str(input).unwrap(), Answer::from(1));
+        for (bad, line) in [
+            ("1-3,5-7,9-8\n4-6", 1),
+            ("1-3\n2-9,3-4,8-x\n4-6", 2),
+            ("1-3,5-7\n2-9,\n4-6", 2),
+            ("1-3\n4-6\n7-9,1-2 3-4", 3),
+        ] {
+            assert!(
+                matches!(parse_assignments(bad), Err(Error::InvalidLine { line: l, .. }) if l == line),
+                "{bad:?}"
+            );
+        }
+
+        let tree = AssignmentTree::new(&assignments);
+        assert_eq!(tree.len(), 8);
+        let elf = |group, elf| ElfId { group, elf };
+        assert_eq!(tree.covering(3), [elf(0, 0), elf(1, 0), elf(1, 1)]);
+        assert!(tree.covering(10).is_empty());
+        assert_eq!(
+            tree.overlapping(interval(7, 8)),
+            [
+                (interval(2, 9), elf(1, 0)),
+                (interval(5, 7), elf(0, 1)),
+                (interval(8, 8), elf(1, 2))
+            ]
+        );
+
+        let data = std::fs::read_to_string("data.txt").unwrap();
+        let assignments = parse_assignments(&data).unwrap();
+        let tree = AssignmentTree::new(&assignments);
+        for (start, end) in [(1, 1), (5, 20), (40, 41), (99, 99), (0, 200)] {
+            let range = interval(start, end);
+            let mut expected: Vec<(Interval, ElfId)> = assignments
+                .iter()
+                .enumerate()
+                .flat_map(|(group, ranges)| {
+                    ranges
+                        .iter()
+                        .enumerate()
+                        .map(move |(index, &sections)| (sections, elf(group, index)))
+                })
+                .filter(|(sections, _)| sections.overlaps(range))
+                .collect();
+            expected.sort_unstable();
+            assert_eq!(tree.overlapping(range), expected);
+        }
+        assert!(AssignmentTree::new(&[])
+            .overlapping(interval(0, 5))
+            .is_empty());
     }
 }
@@ -0,0 +1,116 @@
+use crate::{Interval, SectionAssignment};
+
+/// An elf, by its group (line) and its position in the group, from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ElfId {
+    pub group: usize,
+    pub elf: usize,
+}
+
+/// Every assignment of a camp, indexed to find the ones overlapping a
+/// range in logarithmic time plus the number of matches.
+///
+/// The assignments are sorted by start and laid out as an implicit
+/// balanced binary tree: the middle of every slice is the root of that
+/// slice's subtree, and remembers the largest end found in it so whole
+/// subtrees ending too early can be skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssignmentTree {
+    entries: Vec<(Interval, ElfId)>,
+    max_end: Vec<u32>,
+}
+
+impl AssignmentTree {
+    #[must_use]
+    pub fn new(assignments: &[SectionAssignment]) -> Self {
+        let mut entries: Vec<(Interval, ElfId)> = assignments
+            .iter()
+            .enumerate()
+            .flat_map(|(group, ranges)| {
+                ranges
+                    .iter()
+                    .enumerate()
+                    .map(move |(elf, &range)| (range, ElfId { group, elf }))
+            })
+            .collect();
+        entries.sort_unstable();
+
+        let mut tree = Self {
+            max_end: vec![0; entries.len()],
+            entries,
+        };
+        tree.build(0, tree.entries.len());
+
+        tree
+    }
+
+    /// Fills `max_end` for the subtree over `lo..hi` and returns it.
+    fn build(&mut self, lo: usize, hi: usize) -> Option<u32> {
+        if lo == hi {
+            return None;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let end = [
+            self.build(lo, mid),
+            Some(self.entries[mid].0.end()),
+            self.build(mid + 1, hi),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or_default();
+        self.max_end[mid] = end;
+
+        Some(end)
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The elves assigned to `section`, by their sections' start.
+    #[must_use]
+    pub fn covering(&self, section: u32) -> Vec<ElfId> {
+        Interval::new(section, section).map_or_else(Vec::new, |section| {
+            self.overlapping(section)
+                .into_iter()
+                .map(|(_, elf)| elf)
+                .collect()
+        })
+    }
+
+    /// The assignments sharing a section with `range`, by start.
+    #[must_use]
+    pub fn overlapping(&self, range: Interval) -> Vec<(Interval, ElfId)> {
+        let mut found = vec![];
+        self.search(0, self.entries.len(), range, &mut found);
+
+        found
+    }
+
+    fn search(&self, lo: usize, hi: usize, range: Interval, found: &mut Vec<(Interval, ElfId)>) {
+        if lo == hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        if self.max_end[mid] < range.start() {
+            return;
+        }
+        self.search(lo, mid, range, found);
+        // Everything from `mid` on starts too late.
+        let entry = self.entries[mid];
+        if entry.0.start() > range.end() {
+            return;
+        }
+        if entry.0.overlaps(range) {
+            found.push(entry);
+        }
+        self.search(mid + 1, hi, range, found);
+    }
+}
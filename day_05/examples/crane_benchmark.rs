@@ -0,0 +1,72 @@
+//! Times [`rearrange`] against moving the crates of plain vectors, on a
+//! generated input.
+//!
+//!     cargo run --release -p day_05 --example crane_benchmark -- [STACKS CRATES MOVES [SEED]]
+
+use day_05::{
+    generate_input, rearrange, Crane, CrateMover9000, CrateMover9001, GrabLimitedCrane, Simulation,
+    EMPTY_STACK,
+};
+use std::{env, time::Instant};
+
+/// Every move copies the crates it lifts from one vector to another.
+fn rearrange_vectors(input: &str, crane: &impl Crane) -> String {
+    let simulation = Simulation::new(input, crane).unwrap();
+    let mut stacks = simulation.stacks().clone();
+    for m in simulation.moves() {
+        let from = stacks.get_mut(m.from_index).unwrap();
+        let mut crates = from.split_off(from.len() - m.count);
+        crane.arrange(&mut crates);
+        stacks.get_mut(m.to_index).unwrap().append(&mut crates);
+    }
+
+    stacks
+        .iter()
+        .map(|(_, crates)| {
+            crates
+                .last()
+                .map_or_else(|| EMPTY_STACK.to_string(), |&label| label.to_string())
+        })
+        .collect()
+}
+
+fn time(name: &str, input: &str, crane: &impl Crane) {
+    let start = Instant::now();
+    let expected = rearrange_vectors(input, crane);
+    let vectors = start.elapsed();
+    let start = Instant::now();
+    let answer = rearrange(input, crane).unwrap();
+    let rope = start.elapsed();
+    assert_eq!(answer.to_string(), expected, "{name}: the engines disagree");
+
+    println!("{name:<16} vectors {vectors:>9.1?}   rope {rope:>9.1?}");
+}
+
+fn main() {
+    let args: Vec<u64> = env::args()
+        .skip(1)
+        .map(|arg| arg.parse().expect("arguments are numbers"))
+        .collect();
+    let (stacks, crates, moves, seed) = match args[..] {
+        [] => (9, 1_000_000, 100_000, 0),
+        [stacks, crates, moves] => (stacks, crates, moves, 0),
+        [stacks, crates, moves, seed] => (stacks, crates, moves, seed),
+        _ => panic!("usage: crane_benchmark [STACKS CRATES MOVES [SEED]]"),
+    };
+    let size = |n: u64| usize::try_from(n).expect("arguments fit in memory");
+
+    let start = Instant::now();
+    let input = generate_input(size(stacks), size(crates), size(moves), seed);
+    println!(
+        "{crates} crates on {stacks} stacks and {moves} moves, generated in {:.1?}",
+        start.elapsed()
+    );
+
+    time("CrateMover 9000", &input, &CrateMover9000);
+    time("CrateMover 9001", &input, &CrateMover9001);
+    time(
+        "grabs of 1000",
+        &input,
+        &GrabLimitedCrane { max_per_grab: 1000 },
+    );
+}
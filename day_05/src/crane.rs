@@ -0,0 +1,84 @@
+/// The order a whole lift of crates lands in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Landing {
+    Kept,
+    Reversed,
+    /// Grabs of the given number of crates, taken from the top, each
+    /// keeping its order.
+    Grabs(usize),
+}
+
+/// How a crane puts down the crates it took off a stack.
+pub trait Crane {
+    /// Rearranges `crates`, listed bottom first as they were on the source
+    /// stack, into the order they end up on the destination stack.
+    fn arrange<T>(&self, crates: &mut [T]);
+
+    /// The order every lift lands in, if it does not depend on the lift,
+    /// so the crates can be moved without being looked at one by one.
+    fn landing(&self) -> Option<Landing> {
+        None
+    }
+}
+
+impl<C: Crane + ?Sized> Crane for &C {
+    fn arrange<T>(&self, crates: &mut [T]) {
+        (**self).arrange(crates);
+    }
+
+    fn landing(&self) -> Option<Landing> {
+        (**self).landing()
+    }
+}
+
+/// Moves one crate at a time, so the crates land in reverse order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn arrange<T>(&self, crates: &mut [T]) {
+        crates.reverse();
+    }
+
+    fn landing(&self) -> Option<Landing> {
+        Some(Landing::Reversed)
+    }
+}
+
+/// Moves all the crates at once, keeping their order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn arrange<T>(&self, _: &mut [T]) {}
+
+    fn landing(&self) -> Option<Landing> {
+        Some(Landing::Kept)
+    }
+}
+
+/// Moves at most `max_per_grab` crates at a time, keeping their order
+/// within a grab. A grab of 1 behaves like the [`CrateMover9000`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrabLimitedCrane {
+    pub max_per_grab: usize,
+}
+
+impl Crane for GrabLimitedCrane {
+    fn arrange<T>(&self, crates: &mut [T]) {
+        // The top grab lands first: reversing everything puts the grabs in
+        // landing order, then each grab gets its own order back.
+        crates.reverse();
+        for grab in crates.chunks_mut(self.max_per_grab.max(1)) {
+            grab.reverse();
+        }
+    }
+
+    fn landing(&self) -> Option<Landing> {
+        Some(if self.max_per_grab <= 1 {
+            Landing::Reversed
+        } else {
+            Landing::Grabs(self.max_per_grab)
+        })
+    }
+}
@@ -0,0 +1,196 @@
+use crate::Error;
+
+/// The crates of every stack, bottom first, with the stacks in the order
+/// of the drawing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stacks<'a> {
+    stacks: Vec<(&'a str, Vec<&'a str>)>,
+}
+
+impl<'a> FromIterator<(&'a str, Vec<&'a str>)> for Stacks<'a> {
+    fn from_iter<I: IntoIterator<Item = (&'a str, Vec<&'a str>)>>(iter: I) -> Self {
+        Self {
+            stacks: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> Stacks<'a> {
+    /// Every stack's name and crates, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &[&'a str])> {
+        self.stacks
+            .iter()
+            .map(|(name, crates)| (*name, crates.as_slice()))
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&[&'a str]> {
+        self.iter()
+            .find(|&(stack, _)| stack == name)
+            .map(|(_, crates)| crates)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Vec<&'a str>> {
+        self.stacks
+            .iter_mut()
+            .find(|(stack, _)| *stack == name)
+            .map(|(_, crates)| crates)
+    }
+
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.stacks.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+
+    /// Draws the stacks like the puzzle input does, with the name of every
+    /// stack under it. A column is as wide as its widest cell or name, so
+    /// [`parse_drawing`] reads the drawing back whatever the labels.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let widths: Vec<usize> = self
+            .stacks
+            .iter()
+            .map(|(name, crates)| {
+                crates
+                    .iter()
+                    .map(|label| label.chars().count())
+                    .chain([name.chars().count(), 1])
+                    .max()
+                    .unwrap_or_default()
+                    + 2
+            })
+            .collect();
+        let height = self
+            .stacks
+            .iter()
+            .map(|(_, crates)| crates.len())
+            .max()
+            .unwrap_or_default();
+        let row = |cell: &dyn Fn(usize) -> Option<String>| {
+            let cells: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(index, &width)| format!("{:<width$}", cell(index).unwrap_or_default()))
+                .collect();
+            cells.join(" ") + "\n"
+        };
+
+        // An input needs a row of crates, even if it is empty.
+        let mut drawing: String = (0..height.max(1))
+            .rev()
+            .map(|level| {
+                row(&|index| {
+                    self.stacks[index]
+                        .1
+                        .get(level)
+                        .map(|label| format!("[{label}]"))
+                })
+            })
+            .collect();
+        drawing.push_str(&row(&|index| Some(format!(" {}", self.stacks[index].0))));
+
+        drawing
+    }
+}
+
+/// A run of non-blank characters, with its 1-based column span.
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+}
+
+fn tokens(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut current: Option<(usize, usize)> = None;
+    for (column, (byte, c)) in (1..).zip(line.char_indices().chain([(line.len(), ' ')])) {
+        match (current, c.is_whitespace()) {
+            (None, false) => current = Some((byte, column)),
+            (Some((start_byte, start)), true) => {
+                tokens.push(Token {
+                    text: &line[start_byte..byte],
+                    start,
+                    end: column - 1,
+                });
+                current = None;
+            }
+            _ => {}
+        }
+    }
+
+    tokens
+}
+
+/// Parses the drawing of the stacks: rows of `[label]` cells over a row of
+/// stack names.
+///
+/// Each name marks its stack's column, and a cell belongs to the stack
+/// whose name it sits over. Labels and names may be any number of
+/// characters as long as every cell sits over exactly one name.
+///
+/// # Errors
+///
+/// Will return `Err` if there is no name row, two stacks share a name, a
+/// cell is not a bracketed label or it does not sit over exactly one
+/// stack name.
+pub fn parse_drawing(drawing: &str) -> Result<Stacks<'_>, Error> {
+    let lines: Vec<&str> = drawing.lines().collect();
+    let Some((name_row, crate_rows)) = lines.split_last() else {
+        return Err(Error::InvalidDrawing("no stack names".to_string()));
+    };
+    let names = tokens(name_row);
+    if names.is_empty() {
+        return Err(Error::InvalidDrawing("no stack names".to_string()));
+    }
+    for (index, name) in names.iter().enumerate() {
+        if names[..index].iter().any(|other| other.text == name.text) {
+            return Err(Error::InvalidDrawing(format!(
+                "stack `{}` is named twice",
+                name.text
+            )));
+        }
+    }
+
+    let mut stacks: Vec<(&str, Vec<&str>)> = names.iter().map(|name| (name.text, vec![])).collect();
+    for (line, row) in crate_rows.iter().enumerate().rev() {
+        let mut filled = vec![false; names.len()];
+        for cell in tokens(row) {
+            let misaligned = || Error::MisalignedCell {
+                line: line + 1,
+                column: cell.start,
+                cell: cell.text.to_string(),
+            };
+            let label = cell
+                .text
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .filter(|label| !label.is_empty() && !label.contains(['[', ']']))
+                .ok_or_else(misaligned)?;
+            let mut under = names
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| name.start <= cell.end && cell.start <= name.end)
+                .map(|(index, _)| index);
+            let (Some(stack), None) = (under.next(), under.next()) else {
+                return Err(misaligned());
+            };
+            if filled[stack] {
+                return Err(misaligned());
+            }
+            filled[stack] = true;
+            stacks[stack].1.push(label);
+        }
+    }
+
+    Ok(Stacks { stacks })
+}
@@ -0,0 +1,73 @@
+use crate::{Move, Stacks};
+use std::fmt::Write;
+
+const LABELS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// `SplitMix64`, a small seeded generator.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub const fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, or 0 if `bound` is 0.
+    fn below(&mut self, bound: usize) -> usize {
+        // The modulo bias is irrelevant for test inputs.
+        usize::try_from(self.next() % (bound.max(1) as u64)).unwrap_or_default()
+    }
+}
+
+/// A puzzle input with `crates` lettered crates spread over `stacks` stacks
+/// named from 1, followed by `moves` valid moves, each taking a random
+/// number of crates from a random stack.
+///
+/// The same `seed` always gives the same input.
+#[must_use]
+pub fn generate_input(stacks: usize, crates: usize, moves: usize, seed: u64) -> String {
+    let stacks = stacks.max(1);
+    let mut rng = SplitMix64::new(seed);
+    let names: Vec<String> = (1..=stacks).map(|name| name.to_string()).collect();
+    let mut heights = vec![0; stacks];
+    let mut drawn = vec![vec![]; stacks];
+    for _ in 0..crates {
+        let stack = rng.below(stacks);
+        let letter = rng.below(LABELS.len());
+        drawn[stack].push(&LABELS[letter..=letter]);
+        heights[stack] += 1;
+    }
+
+    let mut input = names
+        .iter()
+        .map(String::as_str)
+        .zip(drawn)
+        .collect::<Stacks<'_>>()
+        .render();
+    input.push('\n');
+    for _ in 0..moves {
+        let from = rng.below(stacks);
+        let to = rng.below(stacks);
+        let count = rng.below(heights[from] + 1);
+        heights[from] -= count;
+        heights[to] += count;
+        let m = Move {
+            count,
+            from_index: &names[from],
+            to_index: &names[to],
+        };
+        let _ = writeln!(input, "{m}");
+    }
+
+    input
+}
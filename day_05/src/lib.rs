@@ -1,82 +1,145 @@
+mod crane;
+mod drawing;
+mod generate;
+mod rope;
+mod simulation;
+
+pub use crane::{Crane, CrateMover9000, CrateMover9001, GrabLimitedCrane, Landing};
+pub use drawing::{parse_drawing, Stacks};
+pub use generate::generate_input;
+pub use rope::RopeStacks;
+pub use simulation::Simulation;
+
+use common::Answer;
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, alpha1, alphanumeric1, multispace1, newline},
-    multi::separated_list1,
-    sequence::delimited,
-    IResult as NomResult,
+    bytes::complete::{is_not, tag},
+    character::complete::{self, line_ending, multispace0},
+    combinator::eof,
+    multi::separated_list0,
+    sequence::{pair, terminated},
+    Err as NomErr, IResult as NomResult,
 };
-use std::{collections::BTreeMap, fs::read_to_string};
+use std::{fmt, fs::read_to_string};
 
 #[derive(Debug)]
 pub enum Error {
     IO(std::io::Error),
     Nom(String),
+    InvalidDrawing(String),
+    /// `cell`, at `column` of `line` (both 1-based), is not a bracketed
+    /// label sitting over exactly one stack name.
+    MisalignedCell {
+        line: usize,
+        column: usize,
+        cell: String,
+    },
+    /// Line `line` (1-based) of the input, `text`, is not a move.
+    InvalidMove {
+        line: usize,
+        text: String,
+    },
+    /// Move `move_number` (1-based) names a stack that does not exist.
+    UnknownStack {
+        move_number: usize,
+        stack: String,
+    },
+    /// Move `move_number` (1-based) takes more crates than `stack` holds.
+    NotEnoughCrates {
+        move_number: usize,
+        stack: String,
+        requested: usize,
+        available: usize,
+    },
 }
 
-#[derive(Debug)]
-struct Move<'a> {
-    count: usize,
-    from_index: &'a str,
-    to_index: &'a str,
+/// Moves `count` crates from the top of stack `from_index` onto stack
+/// `to_index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move<'a> {
+    pub count: usize,
+    pub from_index: &'a str,
+    pub to_index: &'a str,
 }
 
-type Stacks<'a> = BTreeMap<&'a str, Vec<&'a str>>;
-
-fn parse_crate(input: &str) -> NomResult<&str, Option<&str>> {
-    let (input, c) = alt((
-        tag("   "),
-        delimited(complete::char('['), alpha1, complete::char(']')),
-    ))(input)?;
-
-    let result = match c {
-        "   " => None,
-        value => Some(value),
-    };
-    Ok((input, result))
+impl fmt::Display for Move<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count, self.from_index, self.to_index
+        )
+    }
 }
 
-fn parse_move(input: &str) -> NomResult<&str, Move> {
+fn parse_move(input: &str) -> NomResult<&str, Move<'_>> {
     let (input, _) = tag("move ")(input)?;
-    let (input, count) = complete::i64(input)?;
+    let (input, count) = complete::u64(input)?;
     let (input, _) = tag(" from ")(input)?;
-    let (input, from_index) = alphanumeric1(input)?;
+    let (input, from_index) = is_not(" \t\r\n")(input)?;
     let (input, _) = tag(" to ")(input)?;
-    let (input, to_index) = alphanumeric1(input)?;
+    let (input, to_index) = is_not(" \t\r\n")(input)?;
     Ok((
         input,
         Move {
-            count: count.try_into().unwrap_or_default(),
+            count: usize::try_from(count).unwrap_or(usize::MAX),
             from_index,
             to_index,
         },
     ))
 }
 
-fn parse_input(input: &str) -> NomResult<&str, (Stacks, Vec<Move>)> {
-    let (input, crate_rows) =
-        separated_list1(newline, separated_list1(tag(" "), parse_crate))(input)?;
-    let (input, _) = newline(input)?;
-    let (input, stack_names) =
-        separated_list1(tag(" "), delimited(tag(" "), alphanumeric1, tag(" ")))(input)?;
-    let (input, _) = multispace1(input)?;
-    let (input, moves) = separated_list1(newline, parse_move)(input)?;
-
-    let stacks: BTreeMap<&str, Vec<&str>> = stack_names
-        .iter()
-        .enumerate()
-        .map(|(row_index, &stack_name)| {
-            let stack: Vec<&str> = crate_rows
-                .iter()
-                .rev()
-                .filter_map(|row| row[row_index])
-                .collect();
-
-            (stack_name, stack)
-        })
-        .collect();
-
-    Ok((input, (stacks, moves)))
+/// Every move of `input`, up to the end but for trailing blank lines.
+fn parse_moves(input: &str) -> NomResult<&str, Vec<Move<'_>>> {
+    let (input, _) = multispace0(input)?;
+
+    terminated(
+        separated_list0(line_ending, parse_move),
+        pair(multispace0, eof),
+    )(input)
+}
+
+/// Splits `input` at its first blank line into the drawing and the moves.
+fn parse_input(input: &str) -> Result<(Stacks<'_>, Vec<Move<'_>>), Error> {
+    let (drawing, moves) = input
+        .split_once("\n\n")
+        .or_else(|| input.split_once("\r\n\r\n"))
+        .unwrap_or((input, ""));
+    let stacks = parse_drawing(drawing)?;
+    let (_, moves) = parse_moves(moves).map_err(|e| match e {
+        NomErr::Error(e) | NomErr::Failure(e) => {
+            let line = input[..input.len() - e.input.len()].matches('\n').count();
+            Error::InvalidMove {
+                line: line + 1,
+                text: input.lines().nth(line).unwrap_or_default().to_string(),
+            }
+        }
+        NomErr::Incomplete(_) => Error::Nom(e.to_string()),
+    })?;
+
+    Ok((stacks, moves))
+}
+
+/// What a stack holds on top when it is empty, in the message.
+pub const EMPTY_STACK: char = '_';
+
+/// Applies every move of `input` with `crane` and reads the crate on top of
+/// each stack, [`EMPTY_STACK`] standing for the empty ones.
+///
+/// The stacks are [`RopeStacks`], so this scales to millions of moves over
+/// stacks of millions of crates.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input, or a move
+/// names an unknown stack or takes more crates than its stack holds.
+pub fn rearrange(input: &str, crane: &impl Crane) -> Result<Answer, Error> {
+    let (stacks, moves) = parse_input(input)?;
+    let mut rope = RopeStacks::new(&stacks);
+    for (index, m) in moves.iter().enumerate() {
+        rope.apply(index + 1, m, crane)?;
+    }
+
+    Ok(Answer::from(rope.top_crates()))
 }
 
 /// TODO
@@ -85,31 +148,19 @@ fn parse_input(input: &str) -> NomResult<&str, (Stacks, Vec<Move>)> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_five_part_one(path: &str) -> Result<String, Error> {
+pub fn day_five_part_one(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, (mut stacks, moves)) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
-
-    for m in moves {
-        for _ in 0..m.count {
-            let stack_crate;
-            {
-                stack_crate = stacks.get_mut(m.from_index).and_then(std::vec::Vec::pop);
-            }
-            let to = stacks.get_mut(m.to_index);
-            if let Some(to) = to {
-                if let Some(c) = stack_crate {
-                    to.push(c);
-                }
-            }
-        }
-    }
 
-    let message = stacks
-        .iter()
-        .map(|(_, queue)| (*queue.last().expect("needs at least one elem")).to_string())
-        .collect::<Vec<String>>();
+    day_five_part_one_from_str(&input)
+}
 
-    Ok(message.join(""))
+/// Solves [`day_five_part_one`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_five_part_one_from_str(input: &str) -> Result<Answer, Error> {
+    rearrange(input, &CrateMover9000)
 }
 
 /// TODO
@@ -118,64 +169,287 @@ pub fn day_five_part_one(path: &str) -> Result<String, Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_five_part_two(path: &str) -> Result<String, Error> {
+pub fn day_five_part_two(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, (mut stacks, moves)) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
-
-    for m in moves {
-        let stack_crates: Vec<&str>;
-        {
-            // POP count
-            stack_crates = stacks
-                .get_mut(m.from_index)
-                .map(|from| {
-                    let final_length = from.len() - m.count;
-                    let s: Vec<&str> = from.drain(final_length..).collect();
-                    s
-                })
-                .unwrap_or_default();
-        }
-        let to = stacks.get_mut(m.to_index);
-        if let Some(to) = to {
-            for sc in &stack_crates {
-                to.push(*sc);
-            }
-        }
-    }
 
-    let message = stacks
-        .iter()
-        .map(|(_, queue)| (*queue.last().expect("needs at least one elem")).to_string())
-        .collect::<Vec<String>>();
+    day_five_part_two_from_str(&input)
+}
 
-    Ok(message.join(""))
+/// Solves [`day_five_part_two`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_five_part_two_from_str(input: &str) -> Result<Answer, Error> {
+    rearrange(input, &CrateMover9001)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{day_five_part_one, day_five_part_two};
+    use crate::{
+        day_five_part_one, day_five_part_two, generate_input, parse_drawing, parse_input,
+        rearrange, Crane, CrateMover9000, CrateMover9001, Error, GrabLimitedCrane, Move,
+        RopeStacks, Simulation,
+    };
+    use common::Answer;
 
     #[test]
     fn day_five_part_one_example() {
         let result = day_five_part_one("example.txt").unwrap();
-        assert_eq!(result, format!("CMZ"));
+        assert_eq!(result, Answer::from("CMZ"));
     }
 
     #[test]
     fn day_five_part_one_data() {
         let result = day_five_part_one("data.txt").unwrap();
-        assert_eq!(result, format!("SHMSDGZVC"));
+        assert_eq!(result, Answer::from("SHMSDGZVC"));
     }
 
     #[test]
     fn day_five_part_two_example() {
         let result = day_five_part_two("example.txt").unwrap();
-        assert_eq!(result, format!("MCD"));
+        assert_eq!(result, Answer::from("MCD"));
     }
 
     #[test]
     fn day_five_part_two_data() {
         let result = day_five_part_two("data.txt").unwrap();
-        assert_eq!(result, format!("VRZGHDFBQ"));
+        assert_eq!(result, Answer::from("VRZGHDFBQ"));
+    }
+
+    #[test]
+    fn cranes() {
+        let mut crates = ['a', 'b', 'c', 'd', 'e'];
+        GrabLimitedCrane { max_per_grab: 2 }.arrange(&mut crates);
+        assert_eq!(crates, ['d', 'e', 'b', 'c', 'a']);
+
+        let input = std::fs::read_to_string("data.txt").unwrap();
+        let one_at_a_time = GrabLimitedCrane { max_per_grab: 1 };
+        let unlimited = GrabLimitedCrane {
+            max_per_grab: usize::MAX,
+        };
+        assert_eq!(
+            rearrange(&input, &one_at_a_time).unwrap(),
+            rearrange(&input, &CrateMover9000).unwrap()
+        );
+        assert_eq!(
+            rearrange(&input, &unlimited).unwrap(),
+            rearrange(&input, &CrateMover9001).unwrap()
+        );
+
+        let example = std::fs::read_to_string("example.txt").unwrap();
+        let result = rearrange(&example, &GrabLimitedCrane { max_per_grab: 2 }).unwrap();
+        assert_eq!(result, Answer::from("MCZ"));
+    }
+
+    #[test]
+    fn invalid_moves() {
+        let example = std::fs::read_to_string("example.txt").unwrap();
+        let drawing = example.split("move").next().unwrap();
+
+        let result = rearrange(
+            &format!("{drawing}move 3 from 2 to 1\nmove 4 from 2 to 3"),
+            &CrateMover9001,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::NotEnoughCrates {
+                move_number: 2,
+                stack,
+                requested: 4,
+                available: 0
+            }) if stack == "2"
+        ));
+
+        let result = rearrange(&format!("{drawing}move 1 from 4 to 1"), &CrateMover9000);
+        assert!(matches!(
+            result,
+            Err(Error::UnknownStack { move_number: 1, stack }) if stack == "4"
+        ));
+        let result = rearrange(&format!("{drawing}move 1 from 1 to 9"), &CrateMover9000);
+        assert!(matches!(
+            result,
+            Err(Error::UnknownStack { move_number: 1, stack }) if stack == "9"
+        ));
+
+        let result = rearrange(
+            &format!("{drawing}move 3 from 2 to 1\nmove 1 from 3 to 1"),
+            &CrateMover9000,
+        );
+        assert_eq!(result.unwrap(), Answer::from("P__"));
+
+        let result = rearrange(
+            &example.replacen("move 3 from 1 to 3", "mvoe 3 from 1 to 3", 1),
+            &CrateMover9000,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::InvalidMove { line: 7, text }) if text == "mvoe 3 from 1 to 3"
+        ));
+        let result = rearrange(
+            &format!("{drawing}move 1 from 2 to 1 please\n"),
+            &CrateMover9000,
+        );
+        assert!(matches!(result, Err(Error::InvalidMove { line: 6, .. })));
+    }
+
+    #[test]
+    fn simulation_history() {
+        let example = std::fs::read_to_string("example.txt").unwrap();
+        let mut simulation = Simulation::new(&example, CrateMover9000).unwrap();
+        assert_eq!(
+            simulation.render(),
+            example.split("\n\n").next().unwrap().to_string() + "\n"
+        );
+        assert_eq!(simulation.step_back(), None);
+
+        let first = simulation.step_forward().unwrap().unwrap();
+        assert_eq!(
+            first,
+            Move {
+                count: 1,
+                from_index: "2",
+                to_index: "1"
+            }
+        );
+        assert_eq!(first.to_string(), "move 1 from 2 to 1");
+        simulation.step_forward().unwrap();
+        assert_eq!(simulation.position(), 2);
+        assert_eq!(
+            simulation.render(),
+            "        [Z]\n        [N]\n    [C] [D]\n    [M] [P]\n 1   2   3 \n"
+        );
+
+        // Continuing from a serialized state gives the same result.
+        let halfway = simulation.to_input();
+        assert!(halfway.ends_with("\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n"));
+        let mut resumed = Simulation::new(&halfway, CrateMover9000).unwrap();
+        resumed.run().unwrap();
+        simulation.run().unwrap();
+        assert!(simulation.is_done());
+        assert_eq!(resumed.stacks(), simulation.stacks());
+        assert_eq!(simulation.top_crates(), "CMZ");
+        assert_eq!(simulation.step_forward().unwrap(), None);
+
+        let finished_input = simulation.to_input();
+        let finished = Simulation::new(&finished_input, CrateMover9000).unwrap();
+        assert!(finished.moves().is_empty());
+        assert_eq!(finished.stacks(), simulation.stacks());
+
+        while simulation.step_back().is_some() {}
+        assert_eq!(simulation.position(), 0);
+        assert_eq!(simulation.to_input(), example);
+
+        let mut grabbing = Simulation::new(&example, GrabLimitedCrane { max_per_grab: 2 }).unwrap();
+        grabbing.run().unwrap();
+        while grabbing.step_back().is_some() {}
+        assert_eq!(grabbing.to_input(), example);
+
+        let emptied = "[A]\n 1 \n\nmove 1 from 1 to 1\n";
+        let mut simulation = Simulation::new(emptied, CrateMover9001).unwrap();
+        simulation.run().unwrap();
+        assert_eq!(simulation.to_input(), "[A]\n 1 \n\n");
+    }
+
+    #[test]
+    fn column_aware_drawings() {
+        let input = "     [XL]              [B]\n[A]  [BIG]  [C]  [Q]   [D]\n 1    2      3   10     x \n\nmove 1 from 10 to x\nmove 2 from 2 to 1\n";
+        let mut simulation = Simulation::new(input, CrateMover9001).unwrap();
+        let names: Vec<&str> = simulation.stacks().iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["1", "2", "3", "10", "x"]);
+        assert_eq!(simulation.stacks().get("2"), Some(&["BIG", "XL"][..]));
+        assert_eq!(simulation.stacks().get("x"), Some(&["D", "B"][..]));
+        simulation.run().unwrap();
+        assert_eq!(simulation.top_crates(), "XL_C_Q");
+
+        let rendered = simulation.render();
+        assert_eq!(
+            rendered,
+            "[XL]               [Q]\n[BIG]              [B]\n[A]       [C]      [D]\n 1     2   3   10   x \n"
+        );
+        let reparsed = parse_drawing(&rendered).unwrap();
+        assert_eq!(&reparsed, simulation.stacks());
+
+        let drawing = "[A] [B]\n  [C]\n 1   2 \n";
+        assert!(matches!(
+            parse_drawing(drawing),
+            Err(Error::MisalignedCell { line: 2, column: 3, cell }) if cell == "[C]"
+        ));
+        assert!(matches!(
+            parse_drawing("  [A]   \n 1   2 \n"),
+            Err(Error::MisalignedCell {
+                line: 1,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_drawing("[A] B\n 1   2 \n"),
+            Err(Error::MisalignedCell {
+                line: 1,
+                column: 5,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_drawing("[A]\n 1   1 \n"),
+            Err(Error::InvalidDrawing(_))
+        ));
+
+        let example = std::fs::read_to_string("example.txt").unwrap();
+        let crlf = example.replace('\n', "\r\n");
+        assert_eq!(
+            rearrange(&crlf, &CrateMover9000).unwrap(),
+            Answer::from("CMZ")
+        );
+        assert_eq!(
+            rearrange(&crlf, &CrateMover9001).unwrap(),
+            Answer::from("MCD")
+        );
+        let result = rearrange(
+            &crlf.replacen("move 2 from 2", "move 2 from 2 to", 1),
+            &CrateMover9000,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::InvalidMove { line: 8, text }) if text == "move 2 from 2 to to 1"
+        ));
+    }
+
+    /// A crane without a [`crate::Landing`], landing its top crate first.
+    #[derive(Clone, Copy)]
+    struct Rotating;
+
+    impl Crane for Rotating {
+        fn arrange<T>(&self, crates: &mut [T]) {
+            if !crates.is_empty() {
+                crates.rotate_right(1);
+            }
+        }
+    }
+
+    #[test]
+    fn rope_stacks() {
+        fn check(input: &str, crane: &(impl Crane + Copy)) {
+            let mut simulation = Simulation::new(input, *crane).unwrap();
+            simulation.run().unwrap();
+            let (stacks, moves) = parse_input(input).unwrap();
+            let mut rope = RopeStacks::new(&stacks);
+            for (index, m) in moves.iter().enumerate() {
+                rope.apply(index + 1, m, crane).unwrap();
+            }
+            assert_eq!(&rope.to_stacks(), simulation.stacks());
+            assert_eq!(rope.top_crates(), simulation.top_crates());
+        }
+
+        let data = std::fs::read_to_string("data.txt").unwrap();
+        let generated = generate_input(7, 400, 2000, 5);
+        assert_eq!(generated, generate_input(7, 400, 2000, 5));
+        for input in [&data, &generated] {
+            check(input, &CrateMover9000);
+            check(input, &CrateMover9001);
+            check(input, &GrabLimitedCrane { max_per_grab: 3 });
+            check(input, &Rotating);
+        }
     }
 }
@@ -0,0 +1,344 @@
+use crate::{generate::SplitMix64, Crane, Error, Landing, Move, Stacks, EMPTY_STACK};
+use std::collections::HashMap;
+
+const NIL: usize = usize::MAX;
+
+/// A run of crates stored next to each other in the arena, read backwards
+/// when `reversed`. It is also a node of an implicit treap ordering the
+/// runs of a stack from the bottom up.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    start: usize,
+    len: usize,
+    reversed: bool,
+    /// The whole subtree still has to be reversed.
+    flip: bool,
+    priority: u64,
+    left: usize,
+    right: usize,
+    /// Crates in the subtree.
+    total: usize,
+}
+
+/// Crate stacks for long move lists: every stack is a balanced tree of
+/// runs of crates, so a move splits, reverses and joins runs in
+/// logarithmic time instead of touching every crate it moves.
+///
+/// Cranes without a [`Landing`] have their lifts rearranged crate by
+/// crate, as with [`crate::Simulation`]. Grab-limited cranes, landing in
+/// [`Landing::Grabs`], don't get the speedup either: their lifts are split
+/// and joined one grab at a time, so a move costs `O(count / grab · log n)`.
+#[derive(Debug, Clone)]
+pub struct RopeStacks<'a> {
+    arena: Vec<&'a str>,
+    nodes: Vec<Node>,
+    names: Vec<&'a str>,
+    positions: HashMap<&'a str, usize>,
+    roots: Vec<usize>,
+    priorities: SplitMix64,
+    /// How many crates there are in all, which moves never change.
+    crates: usize,
+}
+
+impl<'a> RopeStacks<'a> {
+    #[must_use]
+    pub fn new(stacks: &Stacks<'a>) -> Self {
+        let mut rope = Self {
+            arena: vec![],
+            nodes: vec![],
+            names: vec![],
+            positions: HashMap::new(),
+            roots: vec![],
+            priorities: SplitMix64::new(0x5EED),
+            crates: 0,
+        };
+        for (name, crates) in stacks.iter() {
+            rope.positions.insert(name, rope.names.len());
+            rope.names.push(name);
+            let root = rope.run(crates);
+            rope.roots.push(root);
+        }
+        rope.crates = rope.arena.len();
+
+        rope
+    }
+
+    /// A single node holding `crates`, or `NIL` if there are none.
+    fn run(&mut self, crates: &[&'a str]) -> usize {
+        if crates.is_empty() {
+            return NIL;
+        }
+        let start = self.arena.len();
+        self.arena.extend_from_slice(crates);
+
+        self.node(start, crates.len(), false)
+    }
+
+    fn node(&mut self, start: usize, len: usize, reversed: bool) -> usize {
+        let priority = self.priorities.next();
+        self.nodes.push(Node {
+            start,
+            len,
+            reversed,
+            flip: false,
+            priority,
+            left: NIL,
+            right: NIL,
+            total: len,
+        });
+
+        self.nodes.len() - 1
+    }
+
+    fn total(&self, node: usize) -> usize {
+        if node == NIL {
+            0
+        } else {
+            self.nodes[node].total
+        }
+    }
+
+    fn update(&mut self, node: usize) {
+        let Node {
+            left, right, len, ..
+        } = self.nodes[node];
+        self.nodes[node].total = len + self.total(left) + self.total(right);
+    }
+
+    fn push_down(&mut self, node: usize) {
+        let Node {
+            flip, left, right, ..
+        } = self.nodes[node];
+        if !flip {
+            return;
+        }
+        for child in [left, right] {
+            if child != NIL {
+                self.nodes[child].flip ^= true;
+            }
+        }
+        let node = &mut self.nodes[node];
+        (node.left, node.right) = (right, left);
+        node.reversed ^= true;
+        node.flip = false;
+    }
+
+    /// Splits the crates of `node` into the first `count` and the rest.
+    fn split(&mut self, node: usize, count: usize) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+        self.push_down(node);
+        let Node {
+            start,
+            len,
+            reversed,
+            left,
+            right,
+            ..
+        } = self.nodes[node];
+        let before = self.total(left);
+
+        if count <= before {
+            let (first, rest) = self.split(left, count);
+            self.nodes[node].left = rest;
+            self.update(node);
+            (first, node)
+        } else if count >= before + len {
+            let (first, rest) = self.split(right, count - before - len);
+            self.nodes[node].right = first;
+            self.update(node);
+            (node, rest)
+        } else {
+            // The run itself is cut: the node keeps the first part.
+            let kept = count - before;
+            let (kept_start, rest_start) = if reversed {
+                (start + len - kept, start)
+            } else {
+                (start, start + kept)
+            };
+            let rest = self.node(rest_start, len - kept, reversed);
+            let node_ref = &mut self.nodes[node];
+            node_ref.start = kept_start;
+            node_ref.len = kept;
+            node_ref.right = NIL;
+            self.update(node);
+            (node, self.merge(rest, right))
+        }
+    }
+
+    /// Puts the crates of `b` on top of those of `a`.
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+        if self.nodes[a].priority >= self.nodes[b].priority {
+            self.push_down(a);
+            let right = self.nodes[a].right;
+            self.nodes[a].right = self.merge(right, b);
+            self.update(a);
+            a
+        } else {
+            self.push_down(b);
+            let left = self.nodes[b].left;
+            self.nodes[b].left = self.merge(a, left);
+            self.update(b);
+            b
+        }
+    }
+
+    /// The children of `node` bottom first and whether its run reads
+    /// backwards, once `flip`, the pending flips of its ancestors, and its
+    /// own are applied.
+    fn resolved(&self, node: usize, flip: bool) -> (usize, usize, bool, bool) {
+        let node = &self.nodes[node];
+        let flip = flip ^ node.flip;
+        if flip {
+            (node.right, node.left, !node.reversed, flip)
+        } else {
+            (node.left, node.right, node.reversed, flip)
+        }
+    }
+
+    /// The crates of `node`, bottom first.
+    fn crates(&self, node: usize) -> Vec<&'a str> {
+        let mut crates = Vec::with_capacity(self.total(node));
+        let mut pending = vec![];
+        let (mut current, mut flip) = (node, false);
+        while current != NIL || !pending.is_empty() {
+            while current != NIL {
+                pending.push((current, flip));
+                (current, _, _, flip) = self.resolved(current, flip);
+            }
+            let Some((node, parent_flip)) = pending.pop() else {
+                break;
+            };
+            let (_, right, reversed, node_flip) = self.resolved(node, parent_flip);
+            let Node { start, len, .. } = self.nodes[node];
+            let run = &self.arena[start..start + len];
+            if reversed {
+                crates.extend(run.iter().rev());
+            } else {
+                crates.extend_from_slice(run);
+            }
+            (current, flip) = (right, node_flip);
+        }
+
+        crates
+    }
+
+    /// Copies the crates of every stack into a new arena, dropping the runs
+    /// and nodes left behind by rearranged lifts.
+    fn compact(&mut self) {
+        let stacks = self.to_stacks();
+        self.arena.clear();
+        self.nodes.clear();
+        for (root, (_, crates)) in (0..self.roots.len()).zip(stacks.iter()) {
+            self.roots[root] = self.run(crates);
+        }
+    }
+
+    /// Applies move `move_number` (1-based) with `crane`, leaving the stacks
+    /// untouched if it is not valid.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the move names an unknown stack or takes more
+    /// crates than its stack holds.
+    pub fn apply(
+        &mut self,
+        move_number: usize,
+        m: &Move<'_>,
+        crane: &impl Crane,
+    ) -> Result<(), Error> {
+        let position = |stack: &str| {
+            self.positions
+                .get(stack)
+                .copied()
+                .ok_or_else(|| Error::UnknownStack {
+                    move_number,
+                    stack: stack.to_string(),
+                })
+        };
+        let to = position(m.to_index)?;
+        let from = position(m.from_index)?;
+        let available = self.total(self.roots[from]);
+        let Some(remaining) = available.checked_sub(m.count) else {
+            return Err(Error::NotEnoughCrates {
+                move_number,
+                stack: m.from_index.to_string(),
+                requested: m.count,
+                available,
+            });
+        };
+
+        let (rest, mut lifted) = self.split(self.roots[from], remaining);
+        self.roots[from] = rest;
+        match crane.landing() {
+            Some(Landing::Kept) => {}
+            Some(Landing::Reversed) => {
+                if lifted != NIL {
+                    self.nodes[lifted].flip ^= true;
+                }
+            }
+            Some(Landing::Grabs(size)) => {
+                while lifted != NIL {
+                    let (rest, grab) =
+                        self.split(lifted, self.total(lifted).saturating_sub(size.max(1)));
+                    self.roots[to] = self.merge(self.roots[to], grab);
+                    lifted = rest;
+                }
+            }
+            None => {
+                let mut crates = self.crates(lifted);
+                crane.arrange(&mut crates);
+                lifted = self.run(&crates);
+            }
+        }
+        self.roots[to] = self.merge(self.roots[to], lifted);
+        if self.arena.len() > 2 * self.crates + 1024 {
+            self.compact();
+        }
+
+        Ok(())
+    }
+
+    /// The crate on top of every stack, in stack order, or [`EMPTY_STACK`]
+    /// for the empty ones.
+    #[must_use]
+    pub fn top_crates(&self) -> String {
+        self.roots
+            .iter()
+            .map(|&root| {
+                let (mut node, mut flip) = (root, false);
+                let mut top = None;
+                while node != NIL {
+                    let (_, right, reversed, node_flip) = self.resolved(node, flip);
+                    top = Some((node, reversed));
+                    (node, flip) = (right, node_flip);
+                }
+                top.map_or_else(
+                    || EMPTY_STACK.to_string(),
+                    |(node, reversed)| {
+                        let Node { start, len, .. } = self.nodes[node];
+                        let top = if reversed { start } else { start + len - 1 };
+                        self.arena[top].to_string()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Every stack with all its crates.
+    #[must_use]
+    pub fn to_stacks(&self) -> Stacks<'a> {
+        self.names
+            .iter()
+            .zip(&self.roots)
+            .map(|(&name, &root)| (name, self.crates(root)))
+            .collect()
+    }
+}
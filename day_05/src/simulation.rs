@@ -0,0 +1,158 @@
+use crate::{parse_input, Crane, Error, Move, Stacks, EMPTY_STACK};
+use std::fmt::Write;
+
+/// The stacks part way through the moves of a puzzle input, which can be
+/// stepped through in both directions.
+#[derive(Debug, Clone)]
+pub struct Simulation<'a, C> {
+    crane: C,
+    stacks: Stacks<'a>,
+    moves: Vec<Move<'a>>,
+    /// The crates lifted by every applied move, in their order on the
+    /// source stack, to put them back when stepping back.
+    lifted: Vec<Vec<&'a str>>,
+}
+
+impl<'a, C: Crane> Simulation<'a, C> {
+    /// Starts before the first move of `input`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `input` is not a valid puzzle input.
+    pub fn new(input: &'a str, crane: C) -> Result<Self, Error> {
+        let (stacks, moves) = parse_input(input)?;
+
+        Ok(Self {
+            crane,
+            stacks,
+            moves,
+            lifted: vec![],
+        })
+    }
+
+    #[must_use]
+    pub const fn stacks(&self) -> &Stacks<'a> {
+        &self.stacks
+    }
+
+    /// Every move of the input, applied or not.
+    #[must_use]
+    pub fn moves(&self) -> &[Move<'a>] {
+        &self.moves
+    }
+
+    /// How many moves have been applied.
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.lifted.len()
+    }
+
+    /// Whether every move has been applied.
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        self.position() == self.moves.len()
+    }
+
+    /// Applies the next move and returns it, `None` once they all are.
+    /// An invalid move leaves the stacks untouched.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the move names an unknown stack or takes more
+    /// crates than its stack holds.
+    pub fn step_forward(&mut self) -> Result<Option<Move<'a>>, Error> {
+        let Some(&m) = self.moves.get(self.position()) else {
+            return Ok(None);
+        };
+        let move_number = self.position() + 1;
+        let unknown = |stack: &str| Error::UnknownStack {
+            move_number,
+            stack: stack.to_string(),
+        };
+        if !self.stacks.contains(m.to_index) {
+            return Err(unknown(m.to_index));
+        }
+        let from = self
+            .stacks
+            .get_mut(m.from_index)
+            .ok_or_else(|| unknown(m.from_index))?;
+        let Some(remaining) = from.len().checked_sub(m.count) else {
+            return Err(Error::NotEnoughCrates {
+                move_number,
+                stack: m.from_index.to_string(),
+                requested: m.count,
+                available: from.len(),
+            });
+        };
+
+        let mut crates = from.split_off(remaining);
+        self.lifted.push(crates.clone());
+        self.crane.arrange(&mut crates);
+        if let Some(to) = self.stacks.get_mut(m.to_index) {
+            to.append(&mut crates);
+        }
+
+        Ok(Some(m))
+    }
+
+    /// Undoes the last applied move and returns it, `None` before the
+    /// first one.
+    pub fn step_back(&mut self) -> Option<Move<'a>> {
+        let mut crates = self.lifted.pop()?;
+        let m = self.moves[self.position()];
+        if let Some(to) = self.stacks.get_mut(m.to_index) {
+            to.truncate(to.len() - crates.len());
+        }
+        if let Some(from) = self.stacks.get_mut(m.from_index) {
+            from.append(&mut crates);
+        }
+
+        Some(m)
+    }
+
+    /// Applies every remaining move.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on the first invalid move, see
+    /// [`Simulation::step_forward`].
+    pub fn run(&mut self) -> Result<(), Error> {
+        while self.step_forward()?.is_some() {}
+
+        Ok(())
+    }
+
+    /// The crate on top of every stack, in stack order, or [`EMPTY_STACK`]
+    /// for the empty ones.
+    #[must_use]
+    pub fn top_crates(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(_, stack)| {
+                stack
+                    .last()
+                    .map_or_else(|| EMPTY_STACK.to_string(), |&label| label.to_string())
+            })
+            .collect()
+    }
+
+    /// Draws the stacks like the puzzle input does, see
+    /// [`Stacks::render`].
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.stacks.render()
+    }
+
+    /// A puzzle input starting from the current stacks with the moves that
+    /// are left.
+    #[must_use]
+    pub fn to_input(&self) -> String {
+        let mut input = self.render();
+        input.push('\n');
+        for m in &self.moves[self.position()..] {
+            let _ = writeln!(input, "{m}");
+        }
+
+        input
+    }
+}
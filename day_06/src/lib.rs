@@ -1,3 +1,4 @@
+use common::Answer;
 use itertools::Itertools;
 use std::fs::read_to_string;
 
@@ -13,8 +14,15 @@ pub enum Error {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn find_marker_indexes(path: &str, window_size: usize) -> Result<Vec<usize>, Error> {
+pub fn find_marker_indexes(path: &str, window_size: usize) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
+
+    Ok(find_marker_indexes_from_str(&input, window_size))
+}
+
+/// Solves [`find_marker_indexes`] from the puzzle text in `input`.
+#[must_use]
+pub fn find_marker_indexes_from_str(input: &str, window_size: usize) -> Answer {
     let lines: Vec<&str> = input.lines().collect();
     let marker_indexes: Vec<usize> = lines
         .iter()
@@ -33,34 +41,35 @@ pub fn find_marker_indexes(path: &str, window_size: usize) -> Result<Vec<usize>,
         })
         .collect();
 
-    Ok(marker_indexes)
+    Answer::from(marker_indexes)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::find_marker_indexes;
+    use common::Answer;
 
     #[test]
     fn day_six_part_one_example() {
         let result = find_marker_indexes("example.txt", 4).unwrap();
-        assert_eq!(result, vec![7, 5, 6, 10, 11]);
+        assert_eq!(result, Answer::from(vec![7, 5, 6, 10, 11]));
     }
 
     #[test]
     fn day_six_part_one_data() {
         let result = find_marker_indexes("data.txt", 4).unwrap();
-        assert_eq!(result, vec![1542]);
+        assert_eq!(result, Answer::from(vec![1542]));
     }
 
     #[test]
     fn day_six_part_two_example() {
         let result = find_marker_indexes("example.txt", 14).unwrap();
-        assert_eq!(result, vec![19, 23, 23, 29, 26]);
+        assert_eq!(result, Answer::from(vec![19, 23, 23, 29, 26]));
     }
 
     #[test]
     fn day_six_part_two_data() {
         let result = find_marker_indexes("data.txt", 14).unwrap();
-        assert_eq!(result, vec![3153]);
+        assert_eq!(result, Answer::from(vec![3153]));
     }
 }
@@ -1,3 +1,4 @@
+use common::Answer;
 use std::{cell::RefCell, collections::HashMap, fs::read_to_string, rc::Rc};
 
 #[derive(Debug)]
@@ -10,8 +11,8 @@ pub enum Error {
 struct Dir {
     _name: String,
     size: RefCell<usize>,
-    parent: Option<Rc<Dir>>,
-    subdir: RefCell<HashMap<String, Rc<Dir>>>,
+    parent: Option<Rc<Self>>,
+    subdir: RefCell<HashMap<String, Rc<Self>>>,
 }
 
 impl Dir {
@@ -25,7 +26,7 @@ impl Dir {
     }
 }
 
-fn parse_input<'a>(input: &'a str, root: &'a mut Rc<Dir>) {
+fn parse_input<'a>(input: &'a str, root: &'a Rc<Dir>) {
     let mut cwd = Rc::clone(root);
     for line in input.lines() {
         let words = line.split(' ').collect::<Vec<&str>>();
@@ -64,10 +65,17 @@ fn parse_input<'a>(input: &'a str, root: &'a mut Rc<Dir>) {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_seven_part_one(path: &str) -> Result<usize, Error> {
-    let mut root = Rc::new(Dir::default());
+pub fn day_seven_part_one(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    parse_input(&input, &mut root);
+
+    Ok(day_seven_part_one_from_str(&input))
+}
+
+/// Solves [`day_seven_part_one`] from the puzzle text in `input`.
+#[must_use]
+pub fn day_seven_part_one_from_str(input: &str) -> Answer {
+    let root = Rc::new(Dir::default());
+    parse_input(input, &root);
 
     let mut to_visit = vec![Rc::clone(&root)];
     let mut total = 0;
@@ -81,7 +89,7 @@ pub fn day_seven_part_one(path: &str) -> Result<usize, Error> {
         }
     }
 
-    Ok(total)
+    Answer::from(total)
 }
 
 /// TODO
@@ -90,10 +98,17 @@ pub fn day_seven_part_one(path: &str) -> Result<usize, Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_seven_part_two(path: &str) -> Result<usize, Error> {
-    let mut root = Rc::new(Dir::default());
+pub fn day_seven_part_two(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    parse_input(&input, &mut root);
+
+    Ok(day_seven_part_two_from_str(&input))
+}
+
+/// Solves [`day_seven_part_two`] from the puzzle text in `input`.
+#[must_use]
+pub fn day_seven_part_two_from_str(input: &str) -> Answer {
+    let root = Rc::new(Dir::default());
+    parse_input(input, &root);
 
     let total_size = root.get_size();
     let free_space = 70_000_000 - total_size;
@@ -110,34 +125,35 @@ pub fn day_seven_part_two(path: &str) -> Result<usize, Error> {
             best = best.min(size);
         }
     }
-    Ok(best)
+    Answer::from(best)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{day_seven_part_one, day_seven_part_two};
+    use common::Answer;
 
     #[test]
     fn day_seven_part_one_example() {
         let result = day_seven_part_one("example.txt").unwrap();
-        assert_eq!(result, 95_437);
+        assert_eq!(result, Answer::from(95_437));
     }
 
     #[test]
     fn day_seven_part_one_data() {
         let result = day_seven_part_one("data.txt").unwrap();
-        assert_eq!(result, 1_611_443);
+        assert_eq!(result, Answer::from(1_611_443));
     }
 
     #[test]
     fn day_seven_part_two_example() {
         let result = day_seven_part_two("example.txt").unwrap();
-        assert_eq!(result, 24_933_642);
+        assert_eq!(result, Answer::from(24_933_642));
     }
 
     #[test]
     fn day_seven_part_two_data() {
         let result = day_seven_part_two("data.txt").unwrap();
-        assert_eq!(result, 2_086_088);
+        assert_eq!(result, Answer::from(2_086_088));
     }
 }
@@ -1,3 +1,4 @@
+use common::Answer;
 use std::fs::read_to_string;
 
 /// TODO
@@ -6,11 +7,16 @@ use std::fs::read_to_string;
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_eight_part_one(path: &str) -> Result<usize, std::io::Error> {
-    let grid: Vec<Vec<u8>> = read_to_string(path)?
-        .lines()
-        .map(|line| line.bytes().collect())
-        .collect();
+pub fn day_eight_part_one(path: &str) -> Result<Answer, std::io::Error> {
+    let input = read_to_string(path)?;
+
+    Ok(day_eight_part_one_from_str(&input))
+}
+
+/// Solves [`day_eight_part_one`] from the puzzle text in `input`.
+#[must_use]
+pub fn day_eight_part_one_from_str(input: &str) -> Answer {
+    let grid: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
 
     let mut interior_visibility_count = 0;
     for (y_index, y) in grid.iter().enumerate().skip(1).rev().skip(1).rev() {
@@ -28,8 +34,7 @@ pub fn day_eight_part_one(path: &str) -> Result<usize, std::io::Error> {
                 }
             }
 
-            for x_index in (x_index + 1)..y.len() {
-                let item = grid[y_index][x_index];
+            for &item in grid[y_index].iter().skip(x_index + 1) {
                 if item >= *x {
                     right_visible = false;
                     break;
@@ -61,7 +66,7 @@ pub fn day_eight_part_one(path: &str) -> Result<usize, std::io::Error> {
 
     let visibility_count = interior_visibility_count + ((grid.len() * 2) + (grid[0].len() - 2) * 2);
 
-    Ok(visibility_count)
+    Answer::from(visibility_count)
 }
 
 /// TODO
@@ -70,11 +75,16 @@ pub fn day_eight_part_one(path: &str) -> Result<usize, std::io::Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_eight_part_two(path: &str) -> Result<u64, std::io::Error> {
-    let grid: Vec<Vec<u8>> = read_to_string(path)?
-        .lines()
-        .map(|line| line.bytes().collect())
-        .collect();
+pub fn day_eight_part_two(path: &str) -> Result<Answer, std::io::Error> {
+    let input = read_to_string(path)?;
+
+    Ok(day_eight_part_two_from_str(&input))
+}
+
+/// Solves [`day_eight_part_two`] from the puzzle text in `input`.
+#[must_use]
+pub fn day_eight_part_two_from_str(input: &str) -> Answer {
+    let grid: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
 
     let mut score_grid = vec![vec![0u64; grid[0].len()]; grid.len()];
     for (y_index, y) in grid.iter().enumerate().skip(1).rev().skip(1).rev() {
@@ -92,8 +102,7 @@ pub fn day_eight_part_two(path: &str) -> Result<u64, std::io::Error> {
                 }
             }
 
-            for x_index in (x_index + 1)..y.len() {
-                let item = grid[y_index][x_index];
+            for &item in grid[y_index].iter().skip(x_index + 1) {
                 right_score += 1;
                 if item >= *x {
                     break;
@@ -122,34 +131,35 @@ pub fn day_eight_part_two(path: &str) -> Result<u64, std::io::Error> {
 
     let visibility_count = score_grid.iter().flatten().max().unwrap_or(&0).to_owned();
 
-    Ok(visibility_count)
+    Answer::from(visibility_count)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{day_eight_part_one, day_eight_part_two};
+    use common::Answer;
 
     #[test]
     fn day_eight_part_one_example() {
         let result = day_eight_part_one("example.txt").unwrap();
-        assert_eq!(result, 21);
+        assert_eq!(result, Answer::from(21));
     }
 
     #[test]
     fn day_eight_part_one_data() {
         let result = day_eight_part_one("data.txt").unwrap();
-        assert_eq!(result, 1713);
+        assert_eq!(result, Answer::from(1713));
     }
 
     #[test]
     fn day_eight_part_two_example() {
         let result = day_eight_part_two("example.txt").unwrap();
-        assert_eq!(result, 8);
+        assert_eq!(result, Answer::from(8));
     }
 
     #[test]
     fn day_eight_part_two_data() {
         let result = day_eight_part_two("data.txt").unwrap();
-        assert_eq!(result, 268_464);
+        assert_eq!(result, Answer::from(268_464));
     }
 }
@@ -1,3 +1,4 @@
+use common::Answer;
 use std::{collections::HashSet, fs::read_to_string};
 
 #[derive(Debug)]
@@ -14,8 +15,16 @@ enum Direction {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn simulate_rope_motions(path: &str, rope_length: usize) -> Result<usize, std::io::Error> {
-    let motions: Vec<(Direction, usize)> = read_to_string(path)?
+pub fn simulate_rope_motions(path: &str, rope_length: usize) -> Result<Answer, std::io::Error> {
+    let input = read_to_string(path)?;
+
+    Ok(simulate_rope_motions_from_str(&input, rope_length))
+}
+
+/// Solves [`simulate_rope_motions`] from the puzzle text in `input`.
+#[must_use]
+pub fn simulate_rope_motions_from_str(input: &str, rope_length: usize) -> Answer {
+    let motions: Vec<(Direction, usize)> = input
         .lines()
         .filter_map(|line| {
             if let Some((direction, steps)) = line.split_once(' ') {
@@ -37,9 +46,9 @@ pub fn simulate_rope_motions(path: &str, rope_length: usize) -> Result<usize, st
 
     for (direction, steps) in motions {
         for _ in 0..steps {
-            let head = rope
-                .first_mut()
-                .expect("should always have at least two elements");
+            let Some(head) = rope.first_mut() else {
+                break;
+            };
             match direction {
                 Direction::Right => {
                     head.0 += 1;
@@ -55,9 +64,7 @@ pub fn simulate_rope_motions(path: &str, rope_length: usize) -> Result<usize, st
                 }
             }
             // Update other rope segments
-            let head = *rope
-                .first()
-                .expect("should always have at least two elements");
+            let head = *head;
             let last_index = rope.len() - 1;
             let mut previous_segment = head;
             for (index, segment) in rope.iter_mut().enumerate().skip(1) {
@@ -88,40 +95,41 @@ pub fn simulate_rope_motions(path: &str, rope_length: usize) -> Result<usize, st
         }
     }
 
-    Ok(playing_field.len())
+    Answer::from(playing_field.len())
 }
 
 #[cfg(test)]
 mod tests {
     use crate::simulate_rope_motions;
+    use common::Answer;
 
     #[test]
     fn day_nine_part_one_example() {
         let result = simulate_rope_motions("example.txt", 2).unwrap();
-        assert_eq!(result, 13);
+        assert_eq!(result, Answer::from(13));
     }
 
     #[test]
     fn day_nine_part_one_data() {
         let result = simulate_rope_motions("data.txt", 2).unwrap();
-        assert_eq!(result, 6030);
+        assert_eq!(result, Answer::from(6030));
     }
 
     #[test]
     fn day_nine_part_two_example() {
         let result = simulate_rope_motions("example.txt", 10).unwrap();
-        assert_eq!(result, 1);
+        assert_eq!(result, Answer::from(1));
     }
 
     #[test]
     fn day_nine_part_two_example_two() {
         let result = simulate_rope_motions("example2.txt", 10).unwrap();
-        assert_eq!(result, 36);
+        assert_eq!(result, Answer::from(36));
     }
 
     #[test]
     fn day_nine_part_two_data() {
         let result = simulate_rope_motions("data.txt", 10).unwrap();
-        assert_eq!(result, 2545);
+        assert_eq!(result, Answer::from(2545));
     }
 }
@@ -1,3 +1,4 @@
+use common::Answer;
 use std::fs::read_to_string;
 
 #[derive(Debug)]
@@ -21,8 +22,16 @@ impl Instructions {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_ten_part_one(path: &str) -> Result<isize, std::io::Error> {
-    let instructions: Vec<Instructions> = read_to_string(path)?
+pub fn day_ten_part_one(path: &str) -> Result<Answer, std::io::Error> {
+    let input = read_to_string(path)?;
+
+    Ok(day_ten_part_one_from_str(&input))
+}
+
+/// Solves [`day_ten_part_one`] from the puzzle text in `input`.
+#[must_use]
+pub fn day_ten_part_one_from_str(input: &str) -> Answer {
+    let instructions: Vec<Instructions> = input
         .lines()
         .filter_map(|line| {
             let segments: Vec<&str> = line.split_whitespace().collect();
@@ -61,7 +70,7 @@ pub fn day_ten_part_one(path: &str) -> Result<isize, std::io::Error> {
         }
     }
 
-    Ok(signal_stenth)
+    Answer::from(signal_stenth)
 }
 
 /// TODO
@@ -70,8 +79,16 @@ pub fn day_ten_part_one(path: &str) -> Result<isize, std::io::Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_ten_part_two(path: &str) -> Result<String, std::io::Error> {
-    let instructions: Vec<Instructions> = read_to_string(path)?
+pub fn day_ten_part_two(path: &str) -> Result<Answer, std::io::Error> {
+    let input = read_to_string(path)?;
+
+    Ok(day_ten_part_two_from_str(&input))
+}
+
+/// Solves [`day_ten_part_two`] from the puzzle text in `input`.
+#[must_use]
+pub fn day_ten_part_two_from_str(input: &str) -> Answer {
+    let instructions: Vec<Instructions> = input
         .lines()
         .filter_map(|line| {
             let segments: Vec<&str> = line.split_whitespace().collect();
@@ -121,23 +138,28 @@ pub fn day_ten_part_two(path: &str) -> Result<String, std::io::Error> {
 
     println!("{screen:?}");
 
-    Ok(screen.into_iter().flatten().collect())
+    Answer::image(
+        screen
+            .into_iter()
+            .map(|row| row.into_iter().collect::<String>()),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{day_ten_part_one, day_ten_part_two};
+    use common::Answer;
 
     #[test]
     fn day_nine_part_one_example() {
         let result = day_ten_part_one("example.txt").unwrap();
-        assert_eq!(result, 13140);
+        assert_eq!(result, Answer::from(13140));
     }
 
     #[test]
     fn day_nine_part_one_data() {
         let result = day_ten_part_one("data.txt").unwrap();
-        assert_eq!(result, 14920);
+        assert_eq!(result, Answer::from(14920));
     }
 
     #[test]
@@ -145,7 +167,14 @@ mod tests {
         let result = day_ten_part_two("example.txt").unwrap();
         assert_eq!(
             result,
-            "##..##..##..##..##..##..##..##..##..##..###...###...###...###...###...###...###.####....####....####....####....####....#####.....#####.....#####.....#####.....######......######......######......###########.......#######.......#######....."
+            Answer::image([
+                "##..##..##..##..##..##..##..##..##..##..",
+                "###...###...###...###...###...###...###.",
+                "####....####....####....####....####....",
+                "#####.....#####.....#####.....#####.....",
+                "######......######......######......####",
+                "#######.......#######.......#######.....",
+            ])
         );
     }
 
@@ -154,7 +183,14 @@ mod tests {
         let result = day_ten_part_two("data.txt").unwrap();
         assert_eq!(
             result,
-            "###..#..#..##...##...##..###..#..#.####.#..#.#..#.#..#.#..#.#..#.#..#.#..#....#.###..#..#.#....#..#.#....###..#..#...#..#..#.#..#.#....####.#....#..#.#..#..#...#..#.#..#.#..#.#..#.#..#.#..#.#..#.#....###...##...##..#..#..##..###...##..####."
+            Answer::image([
+                "###..#..#..##...##...##..###..#..#.####.",
+                "#..#.#..#.#..#.#..#.#..#.#..#.#..#....#.",
+                "###..#..#.#....#..#.#....###..#..#...#..",
+                "#..#.#..#.#....####.#....#..#.#..#..#...",
+                "#..#.#..#.#..#.#..#.#..#.#..#.#..#.#....",
+                "###...##...##..#..#..##..###...##..####.",
+            ])
         );
     }
 }
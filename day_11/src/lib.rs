@@ -1,4 +1,5 @@
 #![allow(clippy::iter_with_drain)]
+use common::{Answer, Control};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -13,6 +14,7 @@ use std::fs::read_to_string;
 pub enum Error {
     IO(std::io::Error),
     Nom(String),
+    Cancelled,
 }
 
 #[derive(Debug)]
@@ -146,9 +148,19 @@ fn parse_input(input: &str) -> NomResult<&str, Vec<Monkey>> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_eleven_part_one(path: &str) -> Result<u64, Error> {
+pub fn day_eleven_part_one(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, monkeys) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_eleven_part_one_from_str(&input)
+}
+
+/// Solves [`day_eleven_part_one`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_eleven_part_one_from_str(input: &str) -> Result<Answer, Error> {
+    let (_, monkeys) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
 
     let mut monkey_items: Vec<Vec<u64>> = monkeys.iter().map(|m| m.items.clone()).collect();
     let mut monkey_inspection_count = vec![0u64; monkeys.len()];
@@ -160,15 +172,16 @@ pub fn day_eleven_part_one(path: &str) -> Result<u64, Error> {
             for item in items {
                 let new_worry_level = monkey.operation.operate(item) / 3;
 
-                let target_monkey_index = if new_worry_level % monkey.divisible_test_factor == 0 {
-                    monkeys
-                        .iter()
-                        .position(|m| m.id == monkey.divisible_test_true_outcome_target)
-                } else {
-                    monkeys
-                        .iter()
-                        .position(|m| m.id == monkey.divisible_test_false_outcome_target)
-                };
+                let target_monkey_index =
+                    if new_worry_level.is_multiple_of(monkey.divisible_test_factor) {
+                        monkeys
+                            .iter()
+                            .position(|m| m.id == monkey.divisible_test_true_outcome_target)
+                    } else {
+                        monkeys
+                            .iter()
+                            .position(|m| m.id == monkey.divisible_test_false_outcome_target)
+                    };
 
                 if let Some(index) = target_monkey_index {
                     monkey_items[index].push(new_worry_level);
@@ -181,7 +194,9 @@ pub fn day_eleven_part_one(path: &str) -> Result<u64, Error> {
 
     monkey_inspection_count.sort_unstable();
 
-    Ok(monkey_inspection_count.iter().rev().take(2).product())
+    let monkey_business: u64 = monkey_inspection_count.iter().rev().take(2).product();
+
+    Ok(Answer::from(monkey_business))
 }
 
 /// TODO
@@ -190,31 +205,61 @@ pub fn day_eleven_part_one(path: &str) -> Result<u64, Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_eleven_part_two(path: &str) -> Result<u64, Error> {
+pub fn day_eleven_part_two(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, monkeys) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_eleven_part_two_from_str(&input)
+}
+
+/// Solves [`day_eleven_part_two`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_eleven_part_two_from_str(input: &str) -> Result<Answer, Error> {
+    day_eleven_part_two_with_control(input, &Control::default())
+}
+
+/// Solves [`day_eleven_part_two`] from the puzzle text in `input`,
+/// reporting progress through `control` every hundred rounds.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input or `control`
+/// gets cancelled.
+pub fn day_eleven_part_two_with_control(input: &str, control: &Control) -> Result<Answer, Error> {
+    const ROUNDS: u32 = 10_000;
+
+    let (_, monkeys) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
 
     let mut monkey_items: Vec<Vec<u64>> = monkeys.iter().map(|m| m.items.clone()).collect();
     let mut monkey_inspection_count = vec![0u64; monkeys.len()];
 
     let common_multiplier: u64 = monkeys.iter().map(|m| m.divisible_test_factor).product();
 
-    for _round in 0..10_000 {
+    for round in 0..ROUNDS {
+        if round % 100 == 0 {
+            if control.is_cancelled() {
+                return Err(Error::Cancelled);
+            }
+            control.report("rounds", f64::from(round) / f64::from(ROUNDS));
+        }
         for monkey_index in 0..monkeys.len() {
             let monkey = &monkeys[monkey_index];
             let items: Vec<u64> = monkey_items[monkey_index].drain(0..).collect();
             for item in items {
                 let new_worry_level = monkey.operation.operate(item) % common_multiplier;
 
-                let target_monkey_index = if new_worry_level % monkey.divisible_test_factor == 0 {
-                    monkeys
-                        .iter()
-                        .position(|m| m.id == monkey.divisible_test_true_outcome_target)
-                } else {
-                    monkeys
-                        .iter()
-                        .position(|m| m.id == monkey.divisible_test_false_outcome_target)
-                };
+                let target_monkey_index =
+                    if new_worry_level.is_multiple_of(monkey.divisible_test_factor) {
+                        monkeys
+                            .iter()
+                            .position(|m| m.id == monkey.divisible_test_true_outcome_target)
+                    } else {
+                        monkeys
+                            .iter()
+                            .position(|m| m.id == monkey.divisible_test_false_outcome_target)
+                    };
 
                 if let Some(index) = target_monkey_index {
                     monkey_items[index].push(new_worry_level);
@@ -225,36 +270,63 @@ pub fn day_eleven_part_two(path: &str) -> Result<u64, Error> {
         }
     }
 
+    control.report("rounds", 1.0);
+
     monkey_inspection_count.sort_unstable();
 
-    Ok(monkey_inspection_count.iter().rev().take(2).product())
+    let monkey_business: u64 = monkey_inspection_count.iter().rev().take(2).product();
+
+    Ok(Answer::from(monkey_business))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{day_eleven_part_one, day_eleven_part_two};
+    use crate::{
+        day_eleven_part_one, day_eleven_part_two, day_eleven_part_two_with_control, Error,
+    };
+    use common::{Answer, CancellationToken, Control, Progress};
+    use std::{cell::RefCell, fs::read_to_string};
 
     #[test]
     fn day_eleven_part_one_example() {
         let result = day_eleven_part_one("example.txt").unwrap();
-        assert_eq!(result, 10605);
+        assert_eq!(result, Answer::from(10605));
     }
 
     #[test]
     fn day_eleven_part_one_data() {
         let result = day_eleven_part_one("data.txt").unwrap();
-        assert_eq!(result, 54036);
+        assert_eq!(result, Answer::from(54036));
     }
 
     #[test]
     fn day_eleven_part_two_example() {
         let result = day_eleven_part_two("example.txt").unwrap();
-        assert_eq!(result, 2_713_310_158);
+        assert_eq!(result, Answer::from(2_713_310_158_u64));
     }
 
     #[test]
     fn day_eleven_part_two_data() {
         let result = day_eleven_part_two("data.txt").unwrap();
-        assert_eq!(result, 13_237_873_355);
+        assert_eq!(result, Answer::from(13_237_873_355_u64));
+    }
+
+    #[test]
+    fn day_eleven_part_two_progress_and_cancellation() {
+        let input = read_to_string("example.txt").unwrap();
+        let fractions = RefCell::new(vec![]);
+        let on_progress = |progress: Progress<'_>| fractions.borrow_mut().push(progress.fraction);
+        let control = Control::default().with_progress(&on_progress);
+        let result = day_eleven_part_two_with_control(&input, &control).unwrap();
+        assert_eq!(result, Answer::from(2_713_310_158_u64));
+        let fractions = fractions.into_inner();
+        assert_eq!(fractions.len(), 101);
+        assert_eq!(fractions.first(), Some(&0.0));
+        assert_eq!(fractions.last(), Some(&1.0));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = day_eleven_part_two_with_control(&input, &Control::new(token));
+        assert!(matches!(result, Err(Error::Cancelled)));
     }
 }
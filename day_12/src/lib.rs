@@ -1,3 +1,4 @@
+use common::Answer;
 use nom::{
     character::complete::{newline, one_of},
     multi::{many1, separated_list1},
@@ -26,7 +27,7 @@ impl Pos {
         self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
     }
 
-    fn successors(&self, height_map: &Vec<Vec<i16>>) -> Vec<(Self, usize)> {
+    fn successors(&self, height_map: &[Vec<i16>]) -> Vec<(Self, usize)> {
         let &Self(x, y) = self;
         let mut cross_positions: Vec<(Self, usize)> = vec![];
         let current_height = height_map[y][x];
@@ -74,9 +75,19 @@ impl Pos {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_twelve_part_one(path: &str) -> Result<usize, Error> {
+pub fn day_twelve_part_one(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, height_map) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_twelve_part_one_from_str(&input)
+}
+
+/// Solves [`day_twelve_part_one`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_twelve_part_one_from_str(input: &str) -> Result<Answer, Error> {
+    let (_, height_map) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
 
     let starting_position = height_map.iter().flatten().position(|&c| c == 'S');
     let end_position = height_map.iter().flatten().position(|&c| c == 'E');
@@ -110,11 +121,11 @@ pub fn day_twelve_part_one(path: &str) -> Result<usize, Error> {
         );
 
         if let Some(result) = result {
-            return Ok(result.0.len() - 1);
+            return Ok(Answer::from(result.0.len() - 1));
         }
     }
 
-    Ok(0)
+    Ok(Answer::from(0))
 }
 
 /// TODO
@@ -123,9 +134,19 @@ pub fn day_twelve_part_one(path: &str) -> Result<usize, Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_twelve_part_two(path: &str) -> Result<usize, Error> {
+pub fn day_twelve_part_two(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, height_map) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_twelve_part_two_from_str(&input)
+}
+
+/// Solves [`day_twelve_part_two`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_twelve_part_two_from_str(input: &str) -> Result<Answer, Error> {
+    let (_, height_map) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
 
     let end_position = height_map.iter().flatten().position(|&c| c == 'E');
 
@@ -170,34 +191,35 @@ pub fn day_twelve_part_two(path: &str) -> Result<usize, Error> {
         }
     }
 
-    Ok(shortest_len)
+    Ok(Answer::from(shortest_len))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{day_twelve_part_one, day_twelve_part_two};
+    use common::Answer;
 
     #[test]
     fn day_twelve_part_one_example() {
         let result = day_twelve_part_one("example.txt").unwrap();
-        assert_eq!(result, 31);
+        assert_eq!(result, Answer::from(31));
     }
 
     #[test]
     fn day_twelve_part_one_data() {
         let result = day_twelve_part_one("data.txt").unwrap();
-        assert_eq!(result, 408);
+        assert_eq!(result, Answer::from(408));
     }
 
     #[test]
     fn day_twelve_part_two_example() {
         let result = day_twelve_part_two("example.txt").unwrap();
-        assert_eq!(result, 29);
+        assert_eq!(result, Answer::from(29));
     }
 
     #[test]
     fn day_twelve_part_two_data() {
         let result = day_twelve_part_two("data.txt").unwrap();
-        assert_eq!(result, 399);
+        assert_eq!(result, Answer::from(399));
     }
 }
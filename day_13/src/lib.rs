@@ -1,3 +1,4 @@
+use common::Answer;
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -77,11 +78,21 @@ fn parse_input(input: &str) -> NomResult<&str, Vec<Pair>> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_thirteen_part_one(path: &str) -> Result<usize, Error> {
+pub fn day_thirteen_part_one(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, pairs) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
 
-    let right_order_indexes_sum = pairs
+    day_thirteen_part_one_from_str(&input)
+}
+
+/// Solves [`day_thirteen_part_one`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_thirteen_part_one_from_str(input: &str) -> Result<Answer, Error> {
+    let (_, pairs) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    let right_order_indexes_sum: usize = pairs
         .iter()
         .enumerate()
         .filter_map(|(index, Pair { left, right })| match left.cmp(right) {
@@ -90,7 +101,7 @@ pub fn day_thirteen_part_one(path: &str) -> Result<usize, Error> {
         })
         .sum();
 
-    Ok(right_order_indexes_sum)
+    Ok(Answer::from(right_order_indexes_sum))
 }
 
 /// TODO
@@ -99,19 +110,29 @@ pub fn day_thirteen_part_one(path: &str) -> Result<usize, Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_thirteen_part_two(path: &str) -> Result<usize, Error> {
+pub fn day_thirteen_part_two(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, pairs) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_thirteen_part_two_from_str(&input)
+}
+
+/// Solves [`day_thirteen_part_two`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_thirteen_part_two_from_str(input: &str) -> Result<Answer, Error> {
+    let (_, pairs) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
     let (_, divider_pair) = parse_input("[[2]]\n[[6]]").map_err(|e| Error::Nom(e.to_string()))?;
 
     let mut packets: Vec<Packet> = pairs
         .into_iter()
-        .chain(divider_pair.into_iter())
+        .chain(divider_pair)
         .flat_map(|pair| vec![pair.left, pair.right])
         .collect();
     packets.sort();
 
-    Ok(packets
+    let decoder_key: usize = packets
         .iter()
         .enumerate()
         .filter_map(|(index, p)| match p {
@@ -137,34 +158,37 @@ pub fn day_thirteen_part_two(path: &str) -> Result<usize, Error> {
             Packet::Number(_) => None,
         })
         .inspect(|p| println!("{p}"))
-        .product())
+        .product();
+
+    Ok(Answer::from(decoder_key))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{day_thirteen_part_one, day_thirteen_part_two};
+    use common::Answer;
 
     #[test]
     fn day_thirteen_part_one_example() {
         let result = day_thirteen_part_one("example.txt").unwrap();
-        assert_eq!(result, 13);
+        assert_eq!(result, Answer::from(13));
     }
 
     #[test]
     fn day_thirteen_part_one_data() {
         let result = day_thirteen_part_one("data.txt").unwrap();
-        assert_eq!(result, 6568);
+        assert_eq!(result, Answer::from(6568));
     }
 
     #[test]
     fn day_thirteen_part_two_example() {
         let result = day_thirteen_part_two("example.txt").unwrap();
-        assert_eq!(result, 140);
+        assert_eq!(result, Answer::from(140));
     }
 
     #[test]
     fn day_thirteen_part_two_data() {
         let result = day_thirteen_part_two("data.txt").unwrap();
-        assert_eq!(result, 19493);
+        assert_eq!(result, Answer::from(19493));
     }
 }
@@ -1,3 +1,4 @@
+use common::Answer;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, newline},
@@ -40,7 +41,7 @@ impl Position {
         x_bound: Option<i64>,
         y_bound: i64,
     ) -> (Option<Self>, Move) {
-        let moves = vec![Self::DOWN, Self::DOWN_LEFT, Self::DOWN_RIGHT];
+        let moves = [Self::DOWN, Self::DOWN_LEFT, Self::DOWN_RIGHT];
         for (index, m) in moves.iter().enumerate() {
             let target = Self {
                 x: self.x + m.x,
@@ -49,7 +50,7 @@ impl Position {
             let is_target_free = rested_blocks.get(&target).is_none();
             let is_floor = x_bound.is_none() && target.y == y_bound;
             if is_target_free && !is_floor {
-                let is_in_bounds = x_bound.map_or(true, |x_bound| {
+                let is_in_bounds = x_bound.is_none_or(|x_bound| {
                     target.x >= 0 && target.x <= x_bound && target.y >= 0 && target.y <= y_bound
                 });
                 if is_in_bounds {
@@ -102,9 +103,19 @@ fn parse_input(input: &str) -> NomResult<&str, Vec<Vec<Position>>> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_fourteen_part_one(path: &str) -> Result<usize, Error> {
+pub fn day_fourteen_part_one(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, rock_vectors) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_fourteen_part_one_from_str(&input)
+}
+
+/// Solves [`day_fourteen_part_one`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_fourteen_part_one_from_str(input: &str) -> Result<Answer, Error> {
+    let (_, rock_vectors) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
     let mut rested_positions: BTreeSet<Position> = rock_vectors
         .iter()
         .flat_map(|vectors| {
@@ -137,19 +148,18 @@ pub fn day_fourteen_part_one(path: &str) -> Result<usize, Error> {
         loop {
             let (new_position, move_outcome) =
                 sand_unit.simulate_move(&rested_positions, Some(x_bound), y_bound);
-            match move_outcome {
-                Move::OutOfBounds => {
+            match (move_outcome, new_position) {
+                (Move::OutOfBounds | Move::BlockedSource, _) => {
                     out_of_bounds = true;
                     sand_units -= 1;
                     break;
                 }
-                Move::Settled => {
+                (_, Some(new_position)) => sand_unit = new_position,
+                // Settled.
+                (_, None) => {
                     rested_positions.insert(sand_unit);
                     break;
                 }
-                _ => {
-                    sand_unit = new_position.expect("moved to new position");
-                }
             }
         }
 
@@ -158,7 +168,7 @@ pub fn day_fourteen_part_one(path: &str) -> Result<usize, Error> {
         }
     }
 
-    Ok(sand_units)
+    Ok(Answer::from(sand_units))
 }
 
 /// TODO
@@ -167,9 +177,19 @@ pub fn day_fourteen_part_one(path: &str) -> Result<usize, Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_fourteen_part_two(path: &str) -> Result<usize, Error> {
+pub fn day_fourteen_part_two(path: &str) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, rock_vectors) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_fourteen_part_two_from_str(&input)
+}
+
+/// Solves [`day_fourteen_part_two`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_fourteen_part_two_from_str(input: &str) -> Result<Answer, Error> {
+    let (_, rock_vectors) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
     let mut rested_positions: BTreeSet<Position> = rock_vectors
         .iter()
         .flat_map(|vectors| {
@@ -198,18 +218,17 @@ pub fn day_fourteen_part_two(path: &str) -> Result<usize, Error> {
         loop {
             let (new_position, move_outcome) =
                 sand_unit.simulate_move(&rested_positions, None, y_bound);
-            match move_outcome {
-                Move::BlockedSource => {
+            match (move_outcome, new_position) {
+                (Move::BlockedSource, _) => {
                     blocked_source = true;
                     break;
                 }
-                Move::Settled => {
+                (_, Some(new_position)) => sand_unit = new_position,
+                // Settled, there are no bounds to fall out of.
+                (_, None) => {
                     rested_positions.insert(sand_unit);
                     break;
                 }
-                _ => {
-                    sand_unit = new_position.expect("moved to new position");
-                }
             }
         }
 
@@ -218,34 +237,35 @@ pub fn day_fourteen_part_two(path: &str) -> Result<usize, Error> {
         }
     }
 
-    Ok(sand_units)
+    Ok(Answer::from(sand_units))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{day_fourteen_part_one, day_fourteen_part_two};
+    use common::Answer;
 
     #[test]
     fn day_fourteen_part_one_example() {
         let result = day_fourteen_part_one("example.txt").unwrap();
-        assert_eq!(result, 24);
+        assert_eq!(result, Answer::from(24));
     }
 
     #[test]
     fn day_fourteen_part_one_data() {
         let result = day_fourteen_part_one("data.txt").unwrap();
-        assert_eq!(result, 755);
+        assert_eq!(result, Answer::from(755));
     }
 
     #[test]
     fn day_fourteen_part_two_example() {
         let result = day_fourteen_part_two("example.txt").unwrap();
-        assert_eq!(result, 93);
+        assert_eq!(result, Answer::from(93));
     }
 
     #[test]
     fn day_fourteen_part_two_data() {
         let result = day_fourteen_part_two("data.txt").unwrap();
-        assert_eq!(result, 29805);
+        assert_eq!(result, Answer::from(29805));
     }
 }
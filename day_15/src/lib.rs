@@ -1,3 +1,4 @@
+use common::{Answer, Control};
 use nom::{
     bytes::complete::tag,
     character::complete::{self, newline},
@@ -15,6 +16,7 @@ use std::{
 pub enum Error {
     IO(std::io::Error),
     Nom(String),
+    Cancelled,
 }
 
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Ord, PartialOrd)]
@@ -62,9 +64,19 @@ fn parse_input(input: &str) -> NomResult<&str, Vec<Pair>> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_fifteen_part_one(path: &str, target_y: i64) -> Result<usize, Error> {
+pub fn day_fifteen_part_one(path: &str, target_y: i64) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, sensor_beacon_pairs) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_fifteen_part_one_from_str(&input, target_y)
+}
+
+/// Solves [`day_fifteen_part_one`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_fifteen_part_one_from_str(input: &str, target_y: i64) -> Result<Answer, Error> {
+    let (_, sensor_beacon_pairs) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
 
     let mut grid: HashMap<Position, char> = HashMap::new();
 
@@ -102,7 +114,7 @@ pub fn day_fifteen_part_one(path: &str, target_y: i64) -> Result<usize, Error> {
         }
     }
 
-    Ok(count)
+    Ok(Answer::from(count))
 }
 
 /// TODO
@@ -111,9 +123,43 @@ pub fn day_fifteen_part_one(path: &str, target_y: i64) -> Result<usize, Error> {
 ///
 /// Will return `Err` if `path` does not exist or the user does not have
 /// permission to read it.
-pub fn day_fifteen_part_two(path: &str, upper_bound: i64) -> Result<i64, Error> {
+pub fn day_fifteen_part_two(path: &str, upper_bound: i64) -> Result<Answer, Error> {
     let input = read_to_string(path).map_err(Error::IO)?;
-    let (_, sensor_beacon_pairs) = parse_input(&input).map_err(|e| Error::Nom(e.to_string()))?;
+
+    day_fifteen_part_two_from_str(&input, upper_bound)
+}
+
+/// Solves [`day_fifteen_part_two`] from the puzzle text in `input`.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input.
+pub fn day_fifteen_part_two_from_str(input: &str, upper_bound: i64) -> Result<Answer, Error> {
+    day_fifteen_part_two_with_control(input, upper_bound, &Control::default())
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn fraction(done: usize, total: usize) -> f64 {
+    done as f64 / total.max(1) as f64
+}
+
+/// Solves [`day_fifteen_part_two`] from the puzzle text in `input`,
+/// reporting progress through `control` while the sensors are scanned
+/// (the bulk of the work) and while the rows are searched.
+///
+/// # Errors
+///
+/// Will return `Err` if `input` is not a valid puzzle input or `control`
+/// gets cancelled.
+pub fn day_fifteen_part_two_with_control(
+    input: &str,
+    upper_bound: i64,
+    control: &Control,
+) -> Result<Answer, Error> {
+    const SCANNING_SHARE: f64 = 0.8;
+    const ROWS_PER_REPORT: usize = 100_000;
+
+    let (_, sensor_beacon_pairs) = parse_input(input).map_err(|e| Error::Nom(e.to_string()))?;
 
     let distances: BTreeMap<&Position, i64> = sensor_beacon_pairs
         .iter()
@@ -126,20 +172,25 @@ pub fn day_fifteen_part_two(path: &str, upper_bound: i64) -> Result<i64, Error>
         .collect();
 
     let mut low_high: BTreeMap<i64, Vec<RangeInclusive<i64>>> = BTreeMap::new();
-    for (y, range) in distances.iter().flat_map(|(sensor, max_distance)| {
-        ((sensor.y - max_distance)..(sensor.y + max_distance)).map(|y| {
+    for (index, (sensor, max_distance)) in distances.iter().enumerate() {
+        if control.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+        control.report(
+            "scanning sensors",
+            SCANNING_SHARE * fraction(index, distances.len()),
+        );
+
+        for y in (sensor.y - max_distance)..(sensor.y + max_distance) {
+            if y < 0 || y > upper_bound {
+                continue;
+            }
             let distance_to_line = sensor.y - y;
 
             let max_distance_on_line = *max_distance - distance_to_line.abs();
 
-            (
-                y,
-                ((sensor.x - max_distance_on_line).max(0))
-                    ..=((sensor.x + max_distance_on_line).min(upper_bound)),
-            )
-        })
-    }) {
-        if y >= 0 && y <= upper_bound {
+            let range = ((sensor.x - max_distance_on_line).max(0))
+                ..=((sensor.x + max_distance_on_line).min(upper_bound));
             low_high
                 .entry(y)
                 .and_modify(|lh| lh.push(range.clone()))
@@ -147,57 +198,97 @@ pub fn day_fifteen_part_two(path: &str, upper_bound: i64) -> Result<i64, Error>
         }
     }
 
-    let (x, y) = low_high
-        .into_iter()
-        .find_map(|(key, mut ranges)| {
-            ranges.sort_by(|a, b| a.start().cmp(b.start()));
-            let result: (RangeInclusive<i64>, Option<i64>) =
-                ranges.iter().fold((0..=0, None), |mut acc, range| {
-                    if acc.1.is_some() {
-                        return acc;
-                    }
-                    if acc.0.end() + 1 >= *range.start() {
-                        acc.0 = *acc.0.start()..=(*acc.0.end().max(range.end()));
-                    } else {
-                        acc.1 = Some(acc.0.end() + 1);
-                    }
-
-                    acc
-                });
-            result.1.map(|x| (x, key))
-        })
-        .unwrap_or_default();
+    let rows = low_high.len();
+    let mut distress_beacon = None;
+    for (index, (key, mut ranges)) in low_high.into_iter().enumerate() {
+        if index % ROWS_PER_REPORT == 0 {
+            if control.is_cancelled() {
+                return Err(Error::Cancelled);
+            }
+            control.report(
+                "searching rows",
+                (1.0 - SCANNING_SHARE).mul_add(fraction(index, rows), SCANNING_SHARE),
+            );
+        }
+
+        ranges.sort_by(|a, b| a.start().cmp(b.start()));
+        let result: (RangeInclusive<i64>, Option<i64>) =
+            ranges.iter().fold((0..=0, None), |mut acc, range| {
+                if acc.1.is_some() {
+                    return acc;
+                }
+                if acc.0.end() + 1 >= *range.start() {
+                    acc.0 = *acc.0.start()..=(*acc.0.end().max(range.end()));
+                } else {
+                    acc.1 = Some(acc.0.end() + 1);
+                }
 
-    Ok((x * 4_000_000) + y)
+                acc
+            });
+        if let Some(x) = result.1 {
+            distress_beacon = Some((x, key));
+            break;
+        }
+    }
+    control.report("searching rows", 1.0);
+
+    let (x, y) = distress_beacon.unwrap_or_default();
+
+    Ok(Answer::from((x * 4_000_000) + y))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{day_fifteen_part_one, day_fifteen_part_two};
+    use crate::{
+        day_fifteen_part_one, day_fifteen_part_two, day_fifteen_part_two_with_control, Error,
+    };
+    use common::{Answer, CancellationToken, Control, Progress};
+    use std::{cell::RefCell, fs::read_to_string};
 
     #[test]
     fn day_fifteen_part_one_example() {
         let result = day_fifteen_part_one("example.txt", 10).unwrap();
-        assert_eq!(result, 26);
+        assert_eq!(result, Answer::from(26));
     }
 
     #[test]
     #[ignore = "too slow"]
     fn day_fifteen_part_one_data() {
         let result = day_fifteen_part_one("data.txt", 2_000_000).unwrap();
-        assert_eq!(result, 4_985_193);
+        assert_eq!(result, Answer::from(4_985_193));
     }
 
     #[test]
     fn day_fifteen_part_two_example() {
         let result = day_fifteen_part_two("example.txt", 20).unwrap();
-        assert_eq!(result, 56_000_011);
+        assert_eq!(result, Answer::from(56_000_011));
     }
 
     #[test]
     #[ignore = "too slow"]
     fn day_fifteen_part_two_data() {
         let result = day_fifteen_part_two("data.txt", 4_000_000).unwrap();
-        assert_eq!(result, 11_583_882_601_918);
+        assert_eq!(result, Answer::from(11_583_882_601_918_i64));
+    }
+
+    #[test]
+    fn day_fifteen_part_two_progress_and_cancellation() {
+        let input = read_to_string("example.txt").unwrap();
+        let phases = RefCell::new(vec![]);
+        let on_progress = |progress: Progress<'_>| {
+            let mut phases = phases.borrow_mut();
+            if phases.last() != Some(&progress.phase.to_string()) {
+                phases.push(progress.phase.to_string());
+            }
+        };
+        let control = Control::default().with_progress(&on_progress);
+        let result = day_fifteen_part_two_with_control(&input, 20, &control).unwrap();
+        assert_eq!(result, Answer::from(56_000_011));
+        assert_eq!(phases.into_inner(), ["scanning sensors", "searching rows"]);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = day_fifteen_part_two_with_control(&input, 20, &Control::new(token));
+        assert!(matches!(result, Err(Error::Cancelled)));
     }
 }
@@ -0,0 +1,87 @@
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+const DAYS: u8 = 15;
+
+fn header() -> String {
+    let mut header = String::from(
+        "/* Generated by ffi/build.rs, do not edit. */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern \"C\" {
+#endif
+
+#define AOC_OK 0
+#define AOC_NULL_ARGUMENT 1
+#define AOC_UNKNOWN_PUZZLE 2
+#define AOC_INVALID_UTF8 3
+#define AOC_SOLVE_FAILED 4
+#define AOC_PANICKED 5
+
+/*
+ * Solves `part` (1 or 2) of `day` from the `input_len` bytes at `input`.
+ * On AOC_OK `*answer` holds the answer, otherwise `*error` holds a message.
+ * Both must be released with aoc_free_string.
+ */
+int32_t aoc_solve(uint32_t day, uint32_t part, const char *input, size_t input_len,
+                  char **answer, char **error);
+
+/* Releases a string returned through `answer` or `error`. */
+void aoc_free_string(char *value);
+
+/* Same as aoc_solve for a fixed day and part. */
+",
+    );
+    for day in 1..=DAYS {
+        for part in 1..=2 {
+            let _ = writeln!(
+                header,
+                "int32_t aoc_day{day:02}_part{part}(const char *input, size_t input_len, \
+                 char **answer, char **error);"
+            );
+        }
+    }
+    header.push_str(
+        "
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
+",
+    );
+
+    header
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=tests/harness.c");
+
+    // The header is generated into OUT_DIR, never into the source tree: a
+    // test checks that the copy in include/ matches it.
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("set by cargo"));
+    let include_dir = out_dir.join("include");
+    fs::create_dir_all(&include_dir).expect("can create include dir");
+    fs::write(include_dir.join("aoc.h"), header()).expect("can write header");
+
+    // The C harness calls back into this crate. It is only built for the
+    // tests, which enable `c-harness`, and only tests/c_harness.rs links it.
+    if env::var_os("CARGO_FEATURE_C_HARNESS").is_some()
+        && env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("linux")
+    {
+        cc::Build::new()
+            .file("tests/harness.c")
+            .include(&include_dir)
+            .warnings(true)
+            .extra_warnings(true)
+            .warnings_into_errors(true)
+            .cargo_metadata(false)
+            .compile("aoc_harness");
+        println!("cargo:rustc-link-search=native={}", out_dir.display());
+    }
+}
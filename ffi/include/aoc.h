@@ -0,0 +1,66 @@
+/* Generated by ffi/build.rs, do not edit. */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define AOC_OK 0
+#define AOC_NULL_ARGUMENT 1
+#define AOC_UNKNOWN_PUZZLE 2
+#define AOC_INVALID_UTF8 3
+#define AOC_SOLVE_FAILED 4
+#define AOC_PANICKED 5
+
+/*
+ * Solves `part` (1 or 2) of `day` from the `input_len` bytes at `input`.
+ * On AOC_OK `*answer` holds the answer, otherwise `*error` holds a message.
+ * Both must be released with aoc_free_string.
+ */
+int32_t aoc_solve(uint32_t day, uint32_t part, const char *input, size_t input_len,
+                  char **answer, char **error);
+
+/* Releases a string returned through `answer` or `error`. */
+void aoc_free_string(char *value);
+
+/* Same as aoc_solve for a fixed day and part. */
+int32_t aoc_day01_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day01_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day02_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day02_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day03_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day03_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day04_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day04_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day05_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day05_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day06_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day06_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day07_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day07_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day08_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day08_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day09_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day09_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day10_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day10_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day11_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day11_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day12_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day12_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day13_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day13_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day14_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day14_part2(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day15_part1(const char *input, size_t input_len, char **answer, char **error);
+int32_t aoc_day15_part2(const char *input, size_t input_len, char **answer, char **error);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
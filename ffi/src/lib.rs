@@ -0,0 +1,243 @@
+//! C ABI for every solver. See `include/aoc.h` for the generated header.
+//!
+//! Every entry point takes the puzzle text as a pointer and a length and
+//! returns one of the `AOC_*` status codes. On success `*answer` holds the
+//! answer, otherwise `*error` describes what went wrong. Both strings are
+//! owned by the caller and must be released with `aoc_free_string`.
+
+use client::Part;
+use common::Control;
+use runner::SOLUTIONS;
+use std::{
+    ffi::{c_char, CString},
+    panic, ptr, slice, str,
+};
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_NULL_ARGUMENT: i32 = 1;
+pub const AOC_UNKNOWN_PUZZLE: i32 = 2;
+pub const AOC_INVALID_UTF8: i32 = 3;
+pub const AOC_SOLVE_FAILED: i32 = 4;
+pub const AOC_PANICKED: i32 = 5;
+
+fn into_c_string(value: &str) -> *mut c_char {
+    let value = CString::new(value.replace('\0', "")).unwrap_or_default();
+    value.into_raw()
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "solver panicked".to_string())
+}
+
+unsafe fn solve(
+    day: u32,
+    part: u32,
+    input: *const c_char,
+    input_len: usize,
+    answer: *mut *mut c_char,
+    error: *mut *mut c_char,
+) -> i32 {
+    if answer.is_null() || error.is_null() {
+        return AOC_NULL_ARGUMENT;
+    }
+    *answer = ptr::null_mut();
+    *error = ptr::null_mut();
+
+    let fail = |code: i32, message: String| {
+        *error = into_c_string(&message);
+        code
+    };
+
+    let bytes = match (input.is_null(), input_len) {
+        (_, 0) => &[][..],
+        (true, _) => return fail(AOC_NULL_ARGUMENT, "input is null".to_string()),
+        (false, _) => slice::from_raw_parts(input.cast::<u8>(), input_len),
+    };
+    let Ok(input) = str::from_utf8(bytes) else {
+        return fail(AOC_INVALID_UTF8, "input is not valid UTF-8".to_string());
+    };
+
+    let part = match part {
+        1 => Some(Part::One),
+        2 => Some(Part::Two),
+        _ => None,
+    };
+    let Some(solution) = SOLUTIONS
+        .iter()
+        .find(|solution| u32::from(solution.day) == day && Some(solution.part) == part)
+    else {
+        return fail(AOC_UNKNOWN_PUZZLE, format!("no solver for day {day}"));
+    };
+
+    match panic::catch_unwind(|| (solution.solve)(input, &Control::default())) {
+        Ok(Ok(result)) => {
+            *answer = into_c_string(&result.to_string());
+            AOC_OK
+        }
+        Ok(Err(message)) => fail(AOC_SOLVE_FAILED, message),
+        Err(payload) => fail(AOC_PANICKED, panic_message(payload.as_ref())),
+    }
+}
+
+/// Solves `part` (1 or 2) of `day` from the `input_len` bytes at `input`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes (it may be null when
+/// `input_len` is 0) and `answer` and `error` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input: *const c_char,
+    input_len: usize,
+    answer: *mut *mut c_char,
+    error: *mut *mut c_char,
+) -> i32 {
+    solve(day, part, input, input_len, answer, error)
+}
+
+/// Releases a string returned through `answer` or `error`.
+///
+/// # Safety
+///
+/// `value` must be null or a string handed out by this library that has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+macro_rules! export {
+    ($($name:ident => ($day:literal, $part:literal)),* $(,)?) => {
+        $(
+            /// Same as `aoc_solve` for a fixed day and part.
+            ///
+            /// # Safety
+            ///
+            /// See `aoc_solve`.
+            #[no_mangle]
+            pub unsafe extern "C" fn $name(
+                input: *const c_char,
+                input_len: usize,
+                answer: *mut *mut c_char,
+                error: *mut *mut c_char,
+            ) -> i32 {
+                solve($day, $part, input, input_len, answer, error)
+            }
+        )*
+    };
+}
+
+export! {
+    aoc_day01_part1 => (1, 1),
+    aoc_day01_part2 => (1, 2),
+    aoc_day02_part1 => (2, 1),
+    aoc_day02_part2 => (2, 2),
+    aoc_day03_part1 => (3, 1),
+    aoc_day03_part2 => (3, 2),
+    aoc_day04_part1 => (4, 1),
+    aoc_day04_part2 => (4, 2),
+    aoc_day05_part1 => (5, 1),
+    aoc_day05_part2 => (5, 2),
+    aoc_day06_part1 => (6, 1),
+    aoc_day06_part2 => (6, 2),
+    aoc_day07_part1 => (7, 1),
+    aoc_day07_part2 => (7, 2),
+    aoc_day08_part1 => (8, 1),
+    aoc_day08_part2 => (8, 2),
+    aoc_day09_part1 => (9, 1),
+    aoc_day09_part2 => (9, 2),
+    aoc_day10_part1 => (10, 1),
+    aoc_day10_part2 => (10, 2),
+    aoc_day11_part1 => (11, 1),
+    aoc_day11_part2 => (11, 2),
+    aoc_day12_part1 => (12, 1),
+    aoc_day12_part2 => (12, 2),
+    aoc_day13_part1 => (13, 1),
+    aoc_day13_part2 => (13, 2),
+    aoc_day14_part1 => (14, 1),
+    aoc_day14_part2 => (14, 2),
+    aoc_day15_part1 => (15, 1),
+    aoc_day15_part2 => (15, 2),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{aoc_free_string, aoc_solve, AOC_OK, AOC_UNKNOWN_PUZZLE};
+    use client::Part;
+    use runner::SOLUTIONS;
+    use std::{
+        ffi::{c_char, CStr},
+        ptr,
+    };
+
+    const HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/include/aoc.h"));
+
+    #[test]
+    fn checked_in_header_is_up_to_date() {
+        assert_eq!(
+            include_str!("../include/aoc.h"),
+            HEADER,
+            "copy the generated header over include/aoc.h"
+        );
+    }
+
+    #[test]
+    fn header_declares_every_solution() {
+        for solution in &SOLUTIONS {
+            let part = match solution.part {
+                Part::One => 1,
+                Part::Two => 2,
+            };
+            let name = format!("int32_t aoc_day{:02}_part{part}(", solution.day);
+            assert!(HEADER.contains(&name), "missing {name}");
+        }
+    }
+
+    #[test]
+    fn aoc_solve_works() {
+        let input = "1000\n2000\n\n3000\n";
+        let mut answer: *mut c_char = ptr::null_mut();
+        let mut error: *mut c_char = ptr::null_mut();
+
+        let result = unsafe {
+            aoc_solve(
+                1,
+                1,
+                input.as_ptr().cast(),
+                input.len(),
+                &raw mut answer,
+                &raw mut error,
+            )
+        };
+        assert_eq!(result, AOC_OK);
+        assert!(error.is_null());
+        assert_eq!(unsafe { CStr::from_ptr(answer) }.to_str(), Ok("3000"));
+        unsafe { aoc_free_string(answer) };
+
+        let result = unsafe {
+            aoc_solve(
+                26,
+                1,
+                input.as_ptr().cast(),
+                input.len(),
+                &raw mut answer,
+                &raw mut error,
+            )
+        };
+        assert_eq!(result, AOC_UNKNOWN_PUZZLE);
+        assert!(answer.is_null());
+        assert_eq!(
+            unsafe { CStr::from_ptr(error) }.to_str(),
+            Ok("no solver for day 26")
+        );
+        unsafe { aoc_free_string(error) };
+    }
+}
@@ -0,0 +1,15 @@
+#![cfg(target_os = "linux")]
+
+// The C harness compiled by build.rs calls into the crate.
+extern crate aoc;
+
+#[link(name = "aoc_harness", kind = "static")]
+extern "C" {
+    fn aoc_c_harness() -> i32;
+}
+
+#[test]
+fn c_harness_passes() {
+    let failures = unsafe { aoc_c_harness() };
+    assert_eq!(failures, 0);
+}
@@ -0,0 +1,71 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void expect(int condition, const char *description) {
+    if (!condition) {
+        fprintf(stderr, "harness: %s\n", description);
+        failures++;
+    }
+}
+
+static void expect_answer(int32_t code, char *answer, char *error, const char *expected,
+                          const char *description) {
+    expect(code == AOC_OK, description);
+    expect(error == NULL, description);
+    expect(answer != NULL && strcmp(answer, expected) == 0, description);
+    aoc_free_string(answer);
+    aoc_free_string(error);
+}
+
+static void expect_error(int32_t code, int32_t expected, char *answer, char *error,
+                         const char *description) {
+    expect(code == expected, description);
+    expect(answer == NULL, description);
+    expect(error != NULL && strlen(error) > 0, description);
+    aoc_free_string(answer);
+    aoc_free_string(error);
+}
+
+int aoc_c_harness(void) {
+    const char *calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+    const char *crates = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n"
+                         "move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\n"
+                         "move 1 from 1 to 2\n";
+    const char invalid_utf8[] = {'1', (char)0xff, '\n'};
+    char *answer;
+    char *error;
+    int32_t code;
+
+    code = aoc_day01_part1(calories, strlen(calories), &answer, &error);
+    expect_answer(code, answer, error, "24000", "day 1 part 1");
+
+    code = aoc_solve(1, 2, calories, strlen(calories), &answer, &error);
+    expect_answer(code, answer, error, "45000", "day 1 part 2 through aoc_solve");
+
+    code = aoc_day05_part2(crates, strlen(crates), &answer, &error);
+    expect_answer(code, answer, error, "MCD", "day 5 part 2");
+
+    code = aoc_solve(26, 1, calories, strlen(calories), &answer, &error);
+    expect_error(code, AOC_UNKNOWN_PUZZLE, answer, error, "unknown day");
+
+    code = aoc_solve(1, 3, calories, strlen(calories), &answer, &error);
+    expect_error(code, AOC_UNKNOWN_PUZZLE, answer, error, "unknown part");
+
+    code = aoc_day01_part1(invalid_utf8, sizeof(invalid_utf8), &answer, &error);
+    expect_error(code, AOC_INVALID_UTF8, answer, error, "invalid UTF-8");
+
+    code = aoc_day04_part1("garbage", 7, &answer, &error);
+    expect_error(code, AOC_SOLVE_FAILED, answer, error, "unparseable input");
+
+    code = aoc_day01_part1(NULL, 4, &answer, &error);
+    expect_error(code, AOC_NULL_ARGUMENT, answer, error, "null input");
+
+    code = aoc_day01_part1(calories, strlen(calories), NULL, &error);
+    expect(code == AOC_NULL_ARGUMENT, "null answer pointer");
+
+    return failures;
+}
@@ -0,0 +1,415 @@
+//! A small HTTP dashboard showing the latest results, served on localhost.
+//!
+//! Routes:
+//!
+//! - `GET /` lists every part with its latest answer, timing and whether it
+//!   matches the accepted answer in `answers.json`.
+//! - `GET /results.json` returns the same data as JSON.
+//! - `POST /run` re-runs every day, `POST /run/<day>` a single one.
+//! - `GET /visualizations` lists the SVG files in `visualizations/`, which
+//!   are served from `GET /visualizations/<name>.svg`.
+
+use crate::{format_duration, run, select, workspace_root, Report, SOLUTIONS};
+use client::{Part, Registry};
+use common::Answer;
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+    thread,
+};
+
+/// How the latest answer of a part compares to its accepted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Correct,
+    Incorrect {
+        expected: Answer,
+    },
+    /// There is no accepted answer.
+    Unknown,
+    Failed,
+    NotRun,
+}
+
+impl Verification {
+    #[must_use]
+    pub fn check(report: Option<&Report>, accepted: Option<&Answer>) -> Self {
+        match (report.map(|report| &report.answer), accepted) {
+            (None, _) => Self::NotRun,
+            (Some(Err(_)), _) => Self::Failed,
+            (Some(Ok(_)), None) => Self::Unknown,
+            (Some(Ok(answer)), Some(expected)) if answer == expected => Self::Correct,
+            (Some(Ok(_)), Some(expected)) => Self::Incorrect {
+                expected: expected.clone(),
+            },
+        }
+    }
+
+    const fn label(&self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::Incorrect { .. } => "incorrect",
+            Self::Unknown => "unverified",
+            Self::Failed => "failed",
+            Self::NotRun => "not run",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub location: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            content_type,
+            location: None,
+            body: body.into(),
+        }
+    }
+
+    fn html(body: String) -> Self {
+        Self::new(200, "text/html; charset=utf-8", body)
+    }
+
+    fn error(status: u16) -> Self {
+        Self::new(status, "text/plain; charset=utf-8", reason(status))
+    }
+
+    fn redirect(location: &str) -> Self {
+        Self {
+            location: Some(location.to_string()),
+            ..Self::new(303, "text/plain; charset=utf-8", "")
+        }
+    }
+
+    fn write_to(&self, mut stream: impl Write) -> io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            reason(self.status),
+            self.content_type,
+            self.body.len()
+        )?;
+        if let Some(location) = &self.location {
+            write!(stream, "Location: {location}\r\n")?;
+        }
+        stream.write_all(b"\r\n")?;
+        stream.write_all(&self.body)?;
+        stream.flush()
+    }
+}
+
+const fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        303 => "See Other",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        _ => "Internal Server Error",
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn is_svg(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"))
+}
+
+const fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+pub struct Dashboard {
+    registry: Registry,
+    visualizations: PathBuf,
+    reports: Mutex<BTreeMap<(u8, Part), Report>>,
+    /// Held while solutions run so re-runs don't pile up.
+    running: Mutex<()>,
+}
+
+impl Dashboard {
+    #[must_use]
+    pub fn new(registry: Registry, visualizations: impl Into<PathBuf>) -> Self {
+        Self {
+            registry,
+            visualizations: visualizations.into(),
+            reports: Mutex::new(BTreeMap::new()),
+            running: Mutex::new(()),
+        }
+    }
+
+    /// A dashboard checking against `answers.json` and serving the
+    /// `visualizations` directory of the workspace.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `answers.json` exists but cannot be loaded.
+    pub fn open() -> Result<Self, client::Error> {
+        let registry = Registry::open(workspace_root().join("answers.json"))?;
+        Ok(Self::new(registry, workspace_root().join("visualizations")))
+    }
+
+    /// Solves the selected days (all of them when `days` is empty) and keeps
+    /// their reports. Returns `false` without running anything if another
+    /// run is in progress.
+    pub fn run_days(&self, days: &[u8]) -> bool {
+        let Ok(_running) = self.running.try_lock() else {
+            return false;
+        };
+        for solution in select(days) {
+            let report = run(solution);
+            self.reports
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert((report.day, report.part), report);
+        }
+        true
+    }
+
+    fn rows(&self) -> Vec<(u8, Part, Option<Report>, Verification)> {
+        let reports = self.reports.lock().unwrap_or_else(PoisonError::into_inner);
+        SOLUTIONS
+            .iter()
+            .map(|solution| {
+                let report = reports.get(&(solution.day, solution.part)).cloned();
+                let verification = Verification::check(
+                    report.as_ref(),
+                    self.registry.accepted(solution.day, solution.part),
+                );
+                (solution.day, solution.part, report, verification)
+            })
+            .collect()
+    }
+
+    fn index(&self) -> Response {
+        let mut page = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Advent of code 2022</title>\n<style>\n\
+             body { font-family: sans-serif; }\n\
+             td, th { padding: 0.2em 0.8em; text-align: left; }\n\
+             pre { margin: 0; line-height: 1; }\n\
+             .correct { color: green; } .incorrect, .failed { color: red; }\n\
+             </style>\n</head>\n<body>\n<h1>Advent of code 2022</h1>\n\
+             <form method=\"post\" action=\"/run\"><button>Re-run all</button></form>\n\
+             <p><a href=\"/visualizations\">Visualizations</a> \
+             &middot; <a href=\"/results.json\">JSON</a></p>\n<table>\n\
+             <tr><th>Day</th><th>Part</th><th>Answer</th><th>Time</th><th>Status</th><th></th></tr>\n",
+        );
+
+        for (day, part, report, verification) in self.rows() {
+            let (answer, time) = report.map_or_else(
+                || (String::new(), String::new()),
+                |report| {
+                    let answer = match &report.answer {
+                        Ok(Answer::Image(rows)) => {
+                            format!("<pre>{}</pre>", escape(&rows.join("\n")))
+                        }
+                        Ok(answer) => format!("<code>{}</code>", escape(&answer.to_string())),
+                        Err(error) => escape(error),
+                    };
+                    (answer, format_duration(report.elapsed))
+                },
+            );
+            let status = match &verification {
+                Verification::Incorrect { expected } => {
+                    format!(
+                        "incorrect, expected <code>{}</code>",
+                        escape(&expected.to_string())
+                    )
+                }
+                verification => verification.label().to_string(),
+            };
+            let _ = writeln!(
+                page,
+                "<tr><td>{day}</td><td>{}</td><td>{answer}</td><td>{time}</td>\
+                 <td class=\"{}\">{status}</td>\
+                 <td><form method=\"post\" action=\"/run/{day}\"><button>Re-run</button></form></td></tr>",
+                part_number(part),
+                verification.label()
+            );
+        }
+        page.push_str("</table>\n</body>\n</html>\n");
+
+        Response::html(page)
+    }
+
+    fn results_json(&self) -> Response {
+        let results: Vec<_> = self
+            .rows()
+            .into_iter()
+            .map(|(day, part, report, verification)| {
+                json!({
+                    "day": day,
+                    "part": part_number(part),
+                    "answer": report.as_ref().and_then(|report| report.answer.as_ref().ok()),
+                    "error": report.as_ref().and_then(|report| report.answer.as_ref().err()),
+                    "elapsed_micros": report.as_ref().map(|report| report.elapsed.as_micros()),
+                    "status": verification.label(),
+                })
+            })
+            .collect();
+
+        Response::new(
+            200,
+            "application/json",
+            serde_json::to_string_pretty(&results).unwrap_or_default(),
+        )
+    }
+
+    fn visualization_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(&self.visualizations)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| is_svg(name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn visualizations(&self) -> Response {
+        let mut page = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Visualizations</title>\n</head>\n<body>\n<h1>Visualizations</h1>\n\
+             <p><a href=\"/\">Results</a></p>\n<ul>\n",
+        );
+        let names = self.visualization_names();
+        if names.is_empty() {
+            let _ = writeln!(
+                page,
+                "<li>No SVG files in {}</li>",
+                escape(&self.visualizations.display().to_string())
+            );
+        }
+        for name in names {
+            let name = escape(&name);
+            let _ = writeln!(
+                page,
+                "<li><a href=\"/visualizations/{name}\">{name}</a></li>"
+            );
+        }
+        page.push_str("</ul>\n</body>\n</html>\n");
+
+        Response::html(page)
+    }
+
+    fn visualization(&self, name: &str) -> Response {
+        // Only plain file names, so requests can't escape the directory.
+        let valid = is_svg(name) && !name.starts_with('.') && !name.contains(['/', '\\']);
+        if !valid {
+            return Response::error(404);
+        }
+        fs::read(self.visualizations.join(name)).map_or_else(
+            |_| Response::error(404),
+            |svg| Response::new(200, "image/svg+xml", svg),
+        )
+    }
+
+    fn rerun(&self, days: &[u8]) -> Response {
+        if self.run_days(days) {
+            Response::redirect("/")
+        } else {
+            Response::error(409)
+        }
+    }
+
+    /// Answers a request for `path` without doing any IO on the connection.
+    #[must_use]
+    pub fn route(&self, method: &str, path: &str) -> Response {
+        let path = path.split('?').next().unwrap_or_default();
+        match (method, path) {
+            ("GET", "/") => self.index(),
+            ("GET", "/results.json") => self.results_json(),
+            ("GET", "/visualizations") => self.visualizations(),
+            ("POST", "/run") => self.rerun(&[]),
+            (_, "/" | "/results.json" | "/visualizations" | "/run") => Response::error(405),
+            _ => {
+                if let Some(name) = path.strip_prefix("/visualizations/") {
+                    return match method {
+                        "GET" => self.visualization(name),
+                        _ => Response::error(405),
+                    };
+                }
+                if let Some(day) = path.strip_prefix("/run/") {
+                    return match (method, day.parse::<u8>()) {
+                        ("POST", Ok(day)) if select(&[day]).next().is_some() => self.rerun(&[day]),
+                        ("POST", _) => Response::error(404),
+                        _ => Response::error(405),
+                    };
+                }
+                Response::error(404)
+            }
+        }
+    }
+
+    fn handle(&self, stream: &TcpStream) -> io::Result<()> {
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        // The headers (and any body) are not needed by any route.
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 0 && !header.trim_end().is_empty() {
+            header.clear();
+        }
+
+        let response = match request_line.split_whitespace().collect::<Vec<_>>()[..] {
+            [method, path, _version] => self.route(method, path),
+            _ => Response::error(400),
+        };
+        response.write_to(stream)
+    }
+
+    /// Handles connections on `listener` until it fails, one thread each.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if accepting a connection fails.
+    pub fn serve(self: Arc<Self>, listener: &TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let dashboard = Arc::clone(&self);
+            thread::spawn(move || {
+                if let Err(error) = dashboard.handle(&stream) {
+                    eprintln!("dashboard: {error}");
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Binds `port` on the loopback interface only; 0 picks a free port.
+///
+/// # Errors
+///
+/// Will return `Err` if the port cannot be bound.
+pub fn bind(port: u16) -> io::Result<TcpListener> {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+}
@@ -0,0 +1,110 @@
+use day_01::CalorieLedger;
+use serde_json::json;
+use std::fmt::Write;
+
+/// The percentiles included in both reports.
+const PERCENTILES: [u8; 5] = [10, 25, 50, 75, 90];
+
+/// Renders `ledger` as plain text: summary statistics, the elves with empty
+/// groups, every elf by rank, a histogram with `buckets` buckets and the
+/// tied totals.
+#[must_use]
+pub fn render_table(ledger: &CalorieLedger, buckets: usize) -> String {
+    let mut report = String::new();
+
+    let _ = writeln!(report, "Elves: {}", ledger.elves().len());
+    if let (Some(mean), Some(median)) = (ledger.mean(), ledger.median()) {
+        let _ = writeln!(report, "Mean: {mean:.1}\nMedian: {median:.1}");
+    }
+    for percent in PERCENTILES {
+        if let Some(calories) = ledger.percentile(f64::from(percent)) {
+            let _ = writeln!(report, "p{percent}: {calories}");
+        }
+    }
+
+    let empty = ledger.empty();
+    if !empty.is_empty() {
+        let elves: Vec<String> = empty.iter().map(ToString::to_string).collect();
+        let _ = writeln!(report, "Empty groups: elves {}", elves.join(", "));
+    }
+
+    let _ = writeln!(
+        report,
+        "\n{:>4} {:>4} {:>5} {:>8}",
+        "Rank", "Elf", "Items", "Calories"
+    );
+    for (rank, elf) in ledger.ranked() {
+        let _ = writeln!(
+            report,
+            "{rank:>4} {:>4} {:>5} {:>8}",
+            elf.elf, elf.items, elf.calories
+        );
+    }
+
+    let histogram = ledger.histogram(buckets);
+    let widest = histogram
+        .iter()
+        .map(|bucket| bucket.count)
+        .max()
+        .unwrap_or(1);
+    if !histogram.is_empty() {
+        report.push_str("\nHistogram\n");
+    }
+    for bucket in histogram {
+        let bar = "#".repeat((bucket.count * 40).div_ceil(widest));
+        let _ = writeln!(
+            report,
+            "{:>8}..={:<8} {:>4} {bar}",
+            bucket.start, bucket.end, bucket.count
+        );
+    }
+
+    let ties = ledger.ties();
+    if !ties.is_empty() {
+        report.push_str("\nTies\n");
+    }
+    for tie in ties {
+        let elves: Vec<String> = tie.elves.iter().map(ToString::to_string).collect();
+        let _ = writeln!(report, "{:>8}: elves {}", tie.calories, elves.join(", "));
+    }
+
+    report
+}
+
+/// Renders the same report as [`render_table`] as pretty-printed JSON.
+#[must_use]
+pub fn render_json(ledger: &CalorieLedger, buckets: usize) -> String {
+    let elves: Vec<_> = ledger
+        .ranked()
+        .into_iter()
+        .map(|(rank, elf)| {
+            json!({
+                "rank": rank,
+                "elf": elf.elf,
+                "items": elf.items,
+                "calories": elf.calories,
+            })
+        })
+        .collect();
+    let percentiles: serde_json::Map<String, serde_json::Value> = PERCENTILES
+        .iter()
+        .map(|&percent| {
+            (
+                format!("p{percent}"),
+                json!(ledger.percentile(f64::from(percent))),
+            )
+        })
+        .collect();
+
+    let report = json!({
+        "elves": elves,
+        "mean": ledger.mean(),
+        "median": ledger.median(),
+        "percentiles": percentiles,
+        "histogram": ledger.histogram(buckets),
+        "ties": ledger.ties(),
+        "empty": ledger.empty(),
+    });
+
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
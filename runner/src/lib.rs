@@ -0,0 +1,396 @@
+#[cfg(feature = "memory")]
+mod alloc;
+pub mod dashboard;
+pub mod ledger;
+pub mod readme;
+mod solutions;
+
+pub use solutions::{Solution, Solve, SOLUTIONS};
+
+use client::Part;
+use common::{Answer, CancellationToken, Control, Progress};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Heap usage observed while running a single solution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Highest number of live bytes above what was live before the run.
+    pub peak_bytes: usize,
+    pub allocations: usize,
+    pub allocated_bytes: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Result<Answer, String>,
+    pub elapsed: Duration,
+    /// Only measured when built with the `memory` feature.
+    pub memory: Option<MemoryStats>,
+}
+
+#[must_use]
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+}
+
+#[must_use]
+pub fn input_path(day: u8) -> PathBuf {
+    workspace_root()
+        .join(format!("day_{day:02}"))
+        .join("data.txt")
+}
+
+/// The solutions for `days`, or all of them when `days` is empty.
+pub fn select(days: &[u8]) -> impl Iterator<Item = &'static Solution> + '_ {
+    SOLUTIONS
+        .iter()
+        .filter(move |solution| days.is_empty() || days.contains(&solution.day))
+}
+
+/// Solves `solution` against its `data.txt`, timing it and, when enabled,
+/// measuring its allocations. Reading the input is not measured.
+#[must_use]
+pub fn run(solution: &Solution) -> Report {
+    run_with_control(solution, &Control::default())
+}
+
+/// Same as [`run`], handing `control` to solutions that take one.
+#[must_use]
+pub fn run_with_control(solution: &Solution, control: &Control) -> Report {
+    let path = input_path(solution.day);
+    match fs::read_to_string(&path) {
+        Ok(input) => run_with_input(solution, &input, control),
+        Err(error) => Report {
+            day: solution.day,
+            part: solution.part,
+            answer: Err(format!("could not read {}: {error}", path.display())),
+            elapsed: Duration::ZERO,
+            memory: None,
+        },
+    }
+}
+
+#[must_use]
+pub fn run_with_input(solution: &Solution, input: &str, control: &Control) -> Report {
+    #[cfg(feature = "memory")]
+    let baseline = alloc::start();
+    let start = Instant::now();
+    let answer = (solution.solve)(input, control);
+    let elapsed = start.elapsed();
+    #[cfg(feature = "memory")]
+    let memory = Some(alloc::finish(baseline));
+    #[cfg(not(feature = "memory"))]
+    let memory = None;
+
+    Report {
+        day: solution.day,
+        part: solution.part,
+        answer,
+        elapsed,
+        memory,
+    }
+}
+
+/// Same as [`run`], but gives up on `solution` once it has run for longer
+/// than `timeout`. Cancellable solutions are stopped at that point, the
+/// others only get their answer discarded once they finish.
+#[must_use]
+pub fn run_with_timeout(
+    solution: &Solution,
+    timeout: Duration,
+    on_progress: Option<&dyn Fn(Progress<'_>)>,
+) -> Report {
+    let token = CancellationToken::new();
+    let (finished, watch) = mpsc::channel::<()>();
+    let watchdog = {
+        let token = token.clone();
+        thread::spawn(move || {
+            if watch.recv_timeout(timeout) == Err(mpsc::RecvTimeoutError::Timeout) {
+                token.cancel();
+            }
+        })
+    };
+
+    let mut control = Control::new(token);
+    if let Some(on_progress) = on_progress {
+        control = control.with_progress(on_progress);
+    }
+    let mut report = run_with_control(solution, &control);
+    drop(finished);
+    let _ = watchdog.join();
+
+    if control.is_cancelled() || report.elapsed > timeout {
+        report.answer = Err(format!("timed out after {}", format_duration(timeout)));
+    }
+
+    report
+}
+
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    match micros {
+        0..=999 => format!("{micros}µs"),
+        1_000..=999_999 => format!("{:.2}ms", duration.as_secs_f64() * 1_000.0),
+        _ => format!("{:.2}s", duration.as_secs_f64()),
+    }
+}
+
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dashboard::{self, Dashboard, Verification},
+        format_bytes, format_duration, ledger, readme, run, run_with_timeout, select,
+        workspace_root, MemoryStats, Report, SOLUTIONS,
+    };
+    use client::{Part, Registry};
+    use common::{Answer, Progress};
+    use std::{
+        cell::Cell,
+        fs,
+        io::{Read, Write},
+        net::TcpStream,
+        sync::Arc,
+        thread,
+        time::Duration,
+    };
+
+    #[test]
+    fn solutions_cover_every_part() {
+        for day in 1..=15 {
+            let parts: Vec<Part> = select(&[day]).map(|solution| solution.part).collect();
+            assert_eq!(parts, vec![Part::One, Part::Two]);
+        }
+        assert_eq!(select(&[]).count(), SOLUTIONS.len());
+    }
+
+    #[test]
+    fn run_reports_answer() {
+        let report = run(&SOLUTIONS[0]);
+        assert_eq!(report.answer, Ok(Answer::from(72_511)));
+        #[cfg(feature = "memory")]
+        assert!(report.memory.unwrap().allocations > 0);
+        #[cfg(not(feature = "memory"))]
+        assert!(report.memory.is_none());
+    }
+
+    #[test]
+    fn run_enforces_timeout() {
+        let solution = select(&[11]).nth(1).unwrap();
+        assert!(solution.cancellable);
+        let reports = Cell::new(0);
+        let on_progress = |_: Progress<'_>| reports.set(reports.get() + 1);
+
+        let report = run_with_timeout(solution, Duration::ZERO, Some(&on_progress));
+        assert_eq!(report.answer, Err("timed out after 0µs".to_string()));
+        assert!(reports.get() < 100);
+
+        let report = run_with_timeout(solution, Duration::from_mins(1), None);
+        assert_eq!(report.answer, Ok(Answer::from(13_237_873_355_u64)));
+    }
+
+    #[test]
+    fn ledger_reports() {
+        let groups = day_01::parse_groups("300\n\n100\n200\n\n50\n").unwrap();
+        let with_empty = day_01::CalorieLedger::new(&[vec![], vec![1]]).unwrap();
+        assert!(ledger::render_table(&with_empty, 1).contains("\nEmpty groups: elves 0\n"));
+        let calorie_ledger = day_01::CalorieLedger::new(&groups).unwrap();
+
+        let table = ledger::render_table(&calorie_ledger, 2);
+        assert!(table.starts_with("Elves: 3\nMean: 216.7\nMedian: 300.0\np10: 50\n"));
+        assert!(table.contains("Rank  Elf Items Calories\n   1    0     1      300\n   1    1     2      300\n   3    2     1       50\n"));
+        assert!(table.contains("      50..=175         1 ####################\n"));
+        assert!(table.contains("Ties\n     300: elves 0, 1\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&ledger::render_json(&calorie_ledger, 2)).unwrap();
+        assert_eq!(json["elves"][2]["rank"], 3);
+        assert_eq!(json["percentiles"]["p50"], 300);
+        assert_eq!(json["histogram"][1]["count"], 2);
+        assert_eq!(json["ties"][0]["elves"], serde_json::json!([0, 1]));
+    }
+
+    #[test]
+    fn format_works() {
+        assert_eq!(format_duration(Duration::from_micros(42)), "42µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.50ms");
+        assert_eq!(format_duration(Duration::from_millis(2_346)), "2.35s");
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1_572_864), "1.5MiB");
+    }
+
+    #[test]
+    fn readme_table() {
+        let reports = vec![
+            Report {
+                day: 5,
+                part: Part::One,
+                answer: Ok(Answer::from("CMZ")),
+                elapsed: Duration::from_micros(42),
+                memory: Some(MemoryStats {
+                    peak_bytes: 2048,
+                    allocations: 3,
+                    allocated_bytes: 4096,
+                }),
+            },
+            Report {
+                day: 5,
+                part: Part::Two,
+                answer: Err("IO(NotFound)".to_string()),
+                elapsed: Duration::from_micros(7),
+                memory: None,
+            },
+        ];
+
+        let result = readme::render_table(&reports, false);
+        assert_eq!(
+            result,
+            "| Day | Part | Answer | Time | Peak heap | Allocations | Allocated |\n\
+             | --: | --: | :-- | --: | --: | --: | --: |\n\
+             | 5 | 1 | `CMZ` | 42µs | 2.0KiB | 3 | 4.0KiB |\n\
+             | 5 | 2 | error: IO(NotFound) | 7µs | 0B | 0 | 0B |\n"
+        );
+        let result = readme::render_table(&reports[..1], true);
+        assert!(result.contains("| 5 | 1 | _redacted_ | 42µs |"));
+    }
+
+    #[test]
+    fn readme_section_is_replaced() {
+        let result = readme::update_section("# Title\n", "| table |\n");
+        assert_eq!(
+            result,
+            "# Title\n\n## Results\n\n<!-- results:start -->\n\n| table |\n\n<!-- results:end -->\n"
+        );
+        let result = readme::update_section(&result, "| new |\n");
+        assert_eq!(
+            result,
+            "# Title\n\n## Results\n\n<!-- results:start -->\n\n| new |\n\n<!-- results:end -->\n"
+        );
+    }
+
+    #[test]
+    fn verification_works() {
+        let mut report = run(&SOLUTIONS[0]);
+        let accepted = Answer::from(72_511);
+        assert_eq!(
+            Verification::check(Some(&report), Some(&accepted)),
+            Verification::Correct
+        );
+        assert_eq!(
+            Verification::check(Some(&report), Some(&Answer::from(1))),
+            Verification::Incorrect {
+                expected: Answer::from(1)
+            }
+        );
+        assert_eq!(
+            Verification::check(Some(&report), None),
+            Verification::Unknown
+        );
+        assert_eq!(Verification::check(None, None), Verification::NotRun);
+        report.answer = Err("oops".to_string());
+        assert_eq!(
+            Verification::check(Some(&report), Some(&accepted)),
+            Verification::Failed
+        );
+
+        let registry = Registry::open(workspace_root().join("answers.json")).unwrap();
+        let solution = SOLUTIONS
+            .iter()
+            .find(|solution| solution.day == 10 && solution.part == Part::Two)
+            .unwrap();
+        let report = run(solution);
+        assert!(matches!(report.answer, Ok(Answer::Image(_))));
+        assert_eq!(
+            Verification::check(Some(&report), registry.accepted(10, Part::Two)),
+            Verification::Correct
+        );
+        assert!(matches!(
+            Verification::check(Some(&report), Some(&Answer::from("BUCACBUZ"))),
+            Verification::Incorrect { .. }
+        ));
+    }
+
+    #[test]
+    fn dashboard_routes() {
+        let visualizations = std::env::temp_dir().join("runner-dashboard-routes");
+        fs::create_dir_all(&visualizations).unwrap();
+        fs::write(visualizations.join("rope.svg"), "<svg></svg>").unwrap();
+        let registry = Registry::open(workspace_root().join("answers.json")).unwrap();
+        let dashboard = Dashboard::new(registry, &visualizations);
+
+        let page = dashboard.route("GET", "/");
+        assert_eq!(page.status, 200);
+        assert!(String::from_utf8(page.body).unwrap().contains("not run"));
+
+        let rerun = dashboard.route("POST", "/run/1");
+        assert_eq!(rerun.status, 303);
+        assert_eq!(rerun.location.as_deref(), Some("/"));
+        let page = String::from_utf8(dashboard.route("GET", "/").body).unwrap();
+        assert!(page.contains("<code>72511</code>"));
+        assert!(page.contains("<td class=\"correct\">correct</td>"));
+
+        let svg = dashboard.route("GET", "/visualizations/rope.svg");
+        assert_eq!((svg.status, svg.content_type), (200, "image/svg+xml"));
+        assert_eq!(svg.body, b"<svg></svg>");
+        let list = String::from_utf8(dashboard.route("GET", "/visualizations").body).unwrap();
+        assert!(list.contains("href=\"/visualizations/rope.svg\""));
+
+        assert_eq!(
+            dashboard
+                .route("GET", "/visualizations/../Cargo.toml")
+                .status,
+            404
+        );
+        assert_eq!(dashboard.route("POST", "/run/26").status, 404);
+        assert_eq!(dashboard.route("GET", "/run/1").status, 405);
+        assert_eq!(dashboard.route("GET", "/nope").status, 404);
+    }
+
+    #[test]
+    fn dashboard_serves_http() {
+        let listener = dashboard::bind(0).unwrap();
+        let address = listener.local_addr().unwrap();
+        assert!(address.ip().is_loopback());
+        let dashboard = Arc::new(Dashboard::new(Registry::default(), "missing"));
+        thread::spawn(move || dashboard.serve(&listener));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"GET /results.json HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: application/json\r\n"));
+        assert!(response.contains("\"status\": \"not run\""));
+    }
+}
@@ -0,0 +1,403 @@
+use client::Part;
+use common::{Answer, Progress};
+use runner::{
+    dashboard::{self, Dashboard},
+    format_bytes, format_duration, input_path, ledger, readme, run, run_with_timeout, select,
+    workspace_root, Report, Solution,
+};
+use std::{
+    cell::Cell,
+    fs,
+    io::{self, IsTerminal, Write},
+    process::ExitCode,
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+const USAGE: &str = "Usage:
+    runner [run] [--timeout SECONDS] [DAY...]
+    runner readme [--redact]
+    runner serve [--port PORT]
+    runner ledger [--json] [--buckets N] [FILE]
+    runner interpretations [--rules FILE] [GUIDE]
+    runner tournament [--rounds N] [--seed SEED] [GUIDE]
+
+`run` solves every part of the selected days (all of them by default)
+against their data.txt and prints the answers with timings. With
+`--timeout`, parts still running after that many seconds are reported as
+failed; the long ones are stopped early and show a progress bar meanwhile.
+
+`readme` solves every day and rewrites the results table in README.MD,
+replacing the answers with a placeholder when `--redact` is given.
+
+`serve` solves every day in the background and serves a dashboard of the
+results on http://127.0.0.1:PORT (7878 by default), from which days can be
+re-run and the SVG files in visualizations/ viewed.
+
+`ledger` prints per-elf calorie totals, statistics, a histogram (10 buckets
+by default) and ties for a day 1 input (day_01/data.txt by default), which
+may also be CSV (`elf,calories` rows) or a JSON array of arrays.
+
+`interpretations` scores a day 2 strategy guide (day_02/data.txt by
+default) under every possible reading of its second column, using the
+puzzle's rules or the rules file given with `--rules`.
+
+`tournament` plays day 2 strategies against each other (1000 rounds per
+match by default): the guide's moves, always rock, a frequency counter, a
+last move beater and a seeded random player. It prints the standings and
+every head-to-head score.
+
+Build with `--features memory` to also report heap usage.";
+
+fn parse_days(args: &[String]) -> Result<Vec<u8>, String> {
+    args.iter()
+        .map(|arg| {
+            arg.parse()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| format!("invalid day `{arg}`"))
+        })
+        .collect()
+}
+
+fn print_row(report: &Report) {
+    let part = match report.part {
+        Part::One => 1,
+        Part::Two => 2,
+    };
+    let answer = match &report.answer {
+        Ok(Answer::Image(rows)) => format!("<{}-row image>", rows.len()),
+        Ok(answer) => answer.to_string(),
+        Err(error) => format!("error: {error}"),
+    };
+    let memory = report.memory.map_or_else(String::new, |memory| {
+        format!(
+            " {:>10} {:>10} {:>10}",
+            format_bytes(memory.peak_bytes),
+            memory.allocations,
+            format_bytes(memory.allocated_bytes)
+        )
+    });
+
+    println!(
+        "{:>3} {:>4} {answer:>20} {:>10}{memory}",
+        report.day,
+        part,
+        format_duration(report.elapsed)
+    );
+    if let Ok(Answer::Image(rows)) = &report.answer {
+        for row in rows {
+            println!("{:9}{row}", "");
+        }
+    }
+}
+
+/// Solves `solution` within `timeout`, drawing a progress bar on stderr
+/// while it reports progress.
+fn run_with_progress(solution: &Solution, timeout: Duration) -> Report {
+    const WIDTH: usize = 30;
+
+    let part = match solution.part {
+        Part::One => 1,
+        Part::Two => 2,
+    };
+    let drawn = Cell::new(None);
+    let on_progress = |progress: Progress<'_>| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let percent = (progress.fraction * 100.0).round() as usize;
+        let filled = percent * WIDTH / 100;
+        if drawn.replace(Some(percent)) != Some(percent) {
+            eprint!(
+                "\r  day {} part {part} [{}{}] {percent:>3}% {}\x1b[K",
+                solution.day,
+                "#".repeat(filled),
+                "-".repeat(WIDTH - filled),
+                progress.phase
+            );
+            let _ = io::stderr().flush();
+        }
+    };
+
+    let on_progress: Option<&dyn Fn(Progress<'_>)> = if io::stderr().is_terminal() {
+        Some(&on_progress)
+    } else {
+        None
+    };
+    let report = run_with_timeout(solution, timeout, on_progress);
+    if drawn.get().is_some() {
+        eprint!("\r\x1b[K");
+    }
+
+    report
+}
+
+fn run_days(args: &[String]) -> ExitCode {
+    let (timeout, days) = match args {
+        [flag, seconds, days @ ..] if flag == "--timeout" => (
+            seconds
+                .parse()
+                .ok()
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                .ok_or_else(|| format!("invalid timeout `{seconds}`")),
+            days,
+        ),
+        days => (Ok(Duration::MAX), days),
+    };
+    let (timeout, days) = match (timeout, parse_days(days)) {
+        (Ok(timeout), Ok(days)) => (timeout, days),
+        (Err(error), _) | (_, Err(error)) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let memory_header = if cfg!(feature = "memory") {
+        format!(" {:>10} {:>10} {:>10}", "Peak", "Allocs", "Allocated")
+    } else {
+        String::new()
+    };
+    println!(
+        "{:>3} {:>4} {:>20} {:>10}{memory_header}",
+        "Day", "Part", "Answer", "Time"
+    );
+
+    let mut failed = false;
+    for solution in select(&days) {
+        let report = run_with_progress(solution, timeout);
+        failed |= report.answer.is_err();
+        print_row(&report);
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn update_readme(args: &[String]) -> ExitCode {
+    let redact = match args {
+        [] => false,
+        [flag] if flag == "--redact" => true,
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let reports: Vec<Report> = select(&[])
+        .map(|solution| {
+            let report = run(solution);
+            print_row(&report);
+            report
+        })
+        .collect();
+    let table = readme::render_table(&reports, redact);
+
+    let path = workspace_root().join("README.MD");
+    let result = fs::read_to_string(&path)
+        .and_then(|contents| fs::write(&path, readme::update_section(&contents, &table)));
+    if let Err(error) = result {
+        eprintln!("could not update {}: {error}", path.display());
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn serve(args: &[String]) -> ExitCode {
+    let port = match args {
+        [] => Some(7878),
+        [flag, port] if flag == "--port" => port.parse().ok(),
+        _ => None,
+    };
+    let Some(port) = port else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let dashboard = match Dashboard::open() {
+        Ok(dashboard) => Arc::new(dashboard),
+        Err(error) => {
+            eprintln!("could not load answers.json: {error:?}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let listener = match dashboard::bind(port) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("could not listen on port {port}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Ok(address) = listener.local_addr() {
+        println!("Serving the dashboard on http://{address}");
+    }
+
+    let background = Arc::clone(&dashboard);
+    thread::spawn(move || background.run_days(&[]));
+
+    if let Err(error) = dashboard.serve(&listener) {
+        eprintln!("dashboard stopped: {error}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn calorie_ledger(args: &[String]) -> ExitCode {
+    let mut json = false;
+    let mut buckets = 10;
+    let mut path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--buckets" => {
+                let Some(n) = args.next().and_then(|n| n.parse().ok()) else {
+                    eprintln!("{USAGE}");
+                    return ExitCode::FAILURE;
+                };
+                buckets = n;
+            }
+            _ if path.is_none() => path = Some(arg.into()),
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let path = path.unwrap_or_else(|| input_path(1));
+
+    let calorie_ledger = fs::read_to_string(&path)
+        .map_err(|error| error.to_string())
+        .and_then(|input| {
+            day_01::parse_groups(&input)
+                .and_then(|groups| day_01::CalorieLedger::new(&groups))
+                .map_err(|error| format!("{error:?}"))
+        });
+    let calorie_ledger = match calorie_ledger {
+        Ok(calorie_ledger) => calorie_ledger,
+        Err(error) => {
+            eprintln!("could not read {}: {error}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if json {
+        println!("{}", ledger::render_json(&calorie_ledger, buckets));
+    } else {
+        print!("{}", ledger::render_table(&calorie_ledger, buckets));
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn interpretations(args: &[String]) -> ExitCode {
+    let (rules, guide) = match args {
+        [flag, rules, rest @ ..] if flag == "--rules" => (Some(rules), rest),
+        rest => (None, rest),
+    };
+    let guide = match guide {
+        [] => input_path(2),
+        [guide] => guide.into(),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let game = rules.map_or_else(
+        || Ok(day_02::Game::rock_paper_scissors()),
+        |rules| day_02::Game::load(rules),
+    );
+    let result =
+        game.and_then(|game| day_02::analyze_interpretations(&guide.to_string_lossy(), &game));
+    let interpretations = match result {
+        Ok(interpretations) => interpretations,
+        Err(error) => {
+            eprintln!("could not analyze {}: {error:?}", guide.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let width = interpretations
+        .iter()
+        .map(|interpretation| interpretation.description.len())
+        .max()
+        .unwrap_or_default();
+    println!("{:<width$} {:>8}", "Second column", "Score");
+    for interpretation in interpretations {
+        println!(
+            "{:<width$} {:>8}",
+            interpretation.description, interpretation.score
+        );
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn strategy_tournament(args: &[String]) -> ExitCode {
+    let mut rounds = 1000;
+    let mut seed = 0;
+    let mut guide = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let parsed = match arg.as_str() {
+            "--rounds" => args.next().and_then(|n| n.parse().ok()).map(|n| rounds = n),
+            "--seed" => args.next().and_then(|n| n.parse().ok()).map(|n| seed = n),
+            _ if guide.is_none() => {
+                guide = Some(arg.into());
+                Some(())
+            }
+            _ => None,
+        };
+        if parsed.is_none() {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    }
+    let guide = guide.unwrap_or_else(|| input_path(2));
+
+    let input = match fs::read_to_string(&guide) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("could not read {}: {error}", guide.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let game = day_02::Game::rock_paper_scissors();
+    let rock = game.weapon("rock").unwrap_or(day_02::Choice(0));
+    let mut strategies: Vec<Box<dyn day_02::Strategy>> = vec![
+        Box::new(day_02::FixedGuide::new(
+            &game,
+            &input,
+            &day_02::Match::Choice,
+        )),
+        Box::new(day_02::Always::new(&game, rock)),
+        Box::new(day_02::FrequencyCounter),
+        Box::new(day_02::LastMoveBeater),
+        Box::new(day_02::SeededRandom::new(seed)),
+    ];
+
+    print!("{}", day_02::tournament(&game, &mut strategies, rounds));
+
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Some("readme") => update_readme(&args[1..]),
+        Some("run") => run_days(&args[1..]),
+        Some("serve") => serve(&args[1..]),
+        Some("ledger") => calorie_ledger(&args[1..]),
+        Some("interpretations") => interpretations(&args[1..]),
+        Some("tournament") => strategy_tournament(&args[1..]),
+        _ => run_days(&args),
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{format_bytes, format_duration, Report};
+use client::Part;
+use common::Answer;
+use std::fmt::Write;
+
+pub const START_MARKER: &str = "<!-- results:start -->";
+pub const END_MARKER: &str = "<!-- results:end -->";
+
+fn answer_cell(answer: &Result<Answer, String>, redact: bool) -> String {
+    match answer {
+        Err(error) => format!("error: {}", error.replace('|', "\\|")),
+        Ok(_) if redact => "_redacted_".to_string(),
+        Ok(Answer::Image(rows)) => format!("<pre>{}</pre>", rows.join("<br>")),
+        Ok(answer) => format!("`{answer}`"),
+    }
+}
+
+/// Renders `reports` as a markdown table. Memory columns are only included
+/// when the reports carry memory stats.
+#[must_use]
+pub fn render_table(reports: &[Report], redact: bool) -> String {
+    let with_memory = reports.iter().any(|report| report.memory.is_some());
+
+    let mut table = String::from("| Day | Part | Answer | Time |");
+    if with_memory {
+        table.push_str(" Peak heap | Allocations | Allocated |");
+    }
+    table.push_str("\n| --: | --: | :-- | --: |");
+    if with_memory {
+        table.push_str(" --: | --: | --: |");
+    }
+    table.push('\n');
+
+    for report in reports {
+        let part = match report.part {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+        let _ = write!(
+            table,
+            "| {} | {part} | {} | {} |",
+            report.day,
+            answer_cell(&report.answer, redact),
+            format_duration(report.elapsed)
+        );
+        if with_memory {
+            let memory = report.memory.unwrap_or_default();
+            let _ = write!(
+                table,
+                " {} | {} | {} |",
+                format_bytes(memory.peak_bytes),
+                memory.allocations,
+                format_bytes(memory.allocated_bytes)
+            );
+        }
+        table.push('\n');
+    }
+
+    table
+}
+
+/// Replaces whatever sits between the result markers of `readme` with
+/// `table`, appending a new "Results" section if the markers are missing.
+#[must_use]
+pub fn update_section(readme: &str, table: &str) -> String {
+    let section = format!("{START_MARKER}\n\n{table}\n{END_MARKER}");
+
+    if let (Some(start), Some(end)) = (readme.find(START_MARKER), readme.find(END_MARKER)) {
+        if start < end {
+            return format!(
+                "{}{section}{}",
+                &readme[..start],
+                &readme[end + END_MARKER.len()..]
+            );
+        }
+    }
+
+    format!("{}\n\n## Results\n\n{section}\n", readme.trim_end())
+}
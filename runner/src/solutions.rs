@@ -0,0 +1,106 @@
+use client::Part;
+use common::{Answer, Control};
+
+pub type Solve = fn(&str, &Control) -> Result<Answer, String>;
+
+/// A single puzzle part and how to solve it from its puzzle text.
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub part: Part,
+    pub solve: Solve,
+    /// Whether `solve` stops early when its `Control` gets cancelled.
+    pub cancellable: bool,
+}
+
+/// Lets fallible and infallible solvers share the same table.
+trait IntoSolveResult {
+    fn into_solve_result(self) -> Result<Answer, String>;
+}
+
+impl IntoSolveResult for Answer {
+    fn into_solve_result(self) -> Result<Answer, String> {
+        Ok(self)
+    }
+}
+
+impl<E: std::fmt::Debug> IntoSolveResult for Result<Answer, E> {
+    fn into_solve_result(self) -> Result<Answer, String> {
+        self.map_err(|error| format!("{error:?}"))
+    }
+}
+
+macro_rules! solution {
+    ($day:literal, $part:ident, control: $solve:expr) => {
+        Solution {
+            day: $day,
+            part: Part::$part,
+            solve: |input, control| ($solve)(input, control).into_solve_result(),
+            cancellable: true,
+        }
+    };
+    ($day:literal, $part:ident, $solve:expr) => {
+        Solution {
+            day: $day,
+            part: Part::$part,
+            solve: |input, _| ($solve)(input).into_solve_result(),
+            cancellable: false,
+        }
+    };
+}
+
+/// Every solved puzzle part, in order.
+pub const SOLUTIONS: [Solution; 30] = [
+    solution!(1, One, |input| day_01::calc_max_calories_window_from_str(
+        input, 1
+    )),
+    solution!(1, Two, |input| day_01::calc_max_calories_window_from_str(
+        input, 3
+    )),
+    solution!(2, One, |input| day_02::score_from_match_type_from_str(
+        input,
+        &day_02::Match::Choice
+    )),
+    solution!(2, Two, |input| day_02::score_from_match_type_from_str(
+        input,
+        &day_02::Match::Outcome
+    )),
+    solution!(3, One, day_03::day_three_part_one_from_str),
+    solution!(3, Two, day_03::day_three_part_two_from_str),
+    solution!(4, One, day_04::day_four_part_one_from_str),
+    solution!(4, Two, day_04::day_four_part_two_from_str),
+    solution!(5, One, day_05::day_five_part_one_from_str),
+    solution!(5, Two, day_05::day_five_part_two_from_str),
+    solution!(6, One, |input| day_06::find_marker_indexes_from_str(
+        input, 4
+    )),
+    solution!(6, Two, |input| day_06::find_marker_indexes_from_str(
+        input, 14
+    )),
+    solution!(7, One, day_07::day_seven_part_one_from_str),
+    solution!(7, Two, day_07::day_seven_part_two_from_str),
+    solution!(8, One, day_08::day_eight_part_one_from_str),
+    solution!(8, Two, day_08::day_eight_part_two_from_str),
+    solution!(9, One, |input| day_09::simulate_rope_motions_from_str(
+        input, 2
+    )),
+    solution!(9, Two, |input| day_09::simulate_rope_motions_from_str(
+        input, 10
+    )),
+    solution!(10, One, day_10::day_ten_part_one_from_str),
+    solution!(10, Two, day_10::day_ten_part_two_from_str),
+    solution!(11, One, day_11::day_eleven_part_one_from_str),
+    solution!(11, Two, control: day_11::day_eleven_part_two_with_control),
+    solution!(12, One, day_12::day_twelve_part_one_from_str),
+    solution!(12, Two, day_12::day_twelve_part_two_from_str),
+    solution!(13, One, day_13::day_thirteen_part_one_from_str),
+    solution!(13, Two, day_13::day_thirteen_part_two_from_str),
+    solution!(14, One, day_14::day_fourteen_part_one_from_str),
+    solution!(14, Two, day_14::day_fourteen_part_two_from_str),
+    solution!(15, One, |input| day_15::day_fifteen_part_one_from_str(
+        input, 2_000_000
+    )),
+    solution!(15, Two, control: |input, control| {
+        day_15::day_fifteen_part_two_with_control(input, 4_000_000, control)
+    }),
+];